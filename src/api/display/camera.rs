@@ -0,0 +1,175 @@
+use rand::Rng;
+
+use crate::components::DrawableState;
+
+use super::Point;
+
+/// Maps world coordinates onto the viewport. The world can be larger than the viewport, in which case the camera scrolls to keep its target in view
+#[derive(Debug)]
+pub struct Camera {
+    /// The world coordinate shown at the top left of the viewport
+    pub position: Point<i64>,
+    pub viewport: Point<i64>,
+    pub world_dimensions: Point<i64>,
+    // Added on top of the position, this is kept separate so shaking never moves the camera outside the world bounds permanently
+    shake_offset: Point<i64>,
+}
+
+impl Camera {
+    /// Creates a new camera positioned at the top left of the world
+    ///
+    /// # Arguments
+    ///
+    /// * `viewport` - The dimensions of the area the world is drawn into
+    /// * `world_dimensions` - The dimensions of the world, this should be at least as large as the viewport
+    pub fn new(viewport: Point<i64>, world_dimensions: Point<i64>) -> Self {
+        Self {
+            position: Point::default(),
+            viewport,
+            world_dimensions,
+            shake_offset: Point::default(),
+        }
+    }
+
+    /// Converts a world location into a location relative to the top left of the viewport
+    pub fn world_to_screen(&self, location: Point<i64>) -> Point<i64> {
+        location - self.position + self.shake_offset
+    }
+
+    /// Converts a location relative to the top left of the viewport back into a world location, this is the inverse of world_to_screen
+    pub fn screen_to_world(&self, location: Point<i64>) -> Point<i64> {
+        location + self.position - self.shake_offset
+    }
+
+    /// Moves the camera so the top left of the viewport is at the provided world location, the position is clamped so the viewport never shows anything outside the world
+    pub fn set_position(&mut self, position: Point<i64>) -> &mut Self {
+        let max_position = self.world_dimensions - self.viewport;
+
+        self.position = Point {
+            width: position.width.clamp(0, max_position.width.max(0)),
+            height: position.height.clamp(0, max_position.height.max(0)),
+        };
+
+        self
+    }
+
+    /// Centers the camera on the provided drawable
+    pub fn follow(&mut self, drawable_state: &DrawableState) -> &mut Self {
+        let target_center = drawable_state.get_location()
+            + Point::new(
+                drawable_state.layout.dimensions.width / 2,
+                drawable_state.layout.dimensions.height / 2,
+            );
+
+        self.set_position(
+            target_center - Point::new(self.viewport.width / 2, self.viewport.height / 2),
+        )
+    }
+
+    /// Offsets the camera by a random amount up to the magnitude in each direction, this lasts until the shake is changed or cleared
+    pub fn shake(&mut self, magnitude: i64) -> &mut Self {
+        if magnitude <= 0 {
+            return self.clear_shake();
+        }
+
+        let mut rng = rand::thread_rng();
+
+        self.shake_offset = Point {
+            width: rng.gen_range(-magnitude..=magnitude),
+            height: rng.gen_range(-magnitude..=magnitude),
+        };
+
+        self
+    }
+
+    pub fn clear_shake(&mut self) -> &mut Self {
+        self.shake_offset = Point::default();
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::{Layout, Point},
+        components::{DrawableState, DrawableType},
+    };
+
+    use super::Camera;
+
+    const VIEWPORT: Point<i64> = Point::new(20, 10);
+
+    fn get_drawable_mock(location: Point<i64>) -> DrawableState {
+        DrawableState::new(
+            Layout::new(&Point::new(2, 2), None),
+            location,
+            DrawableType::Player,
+            None,
+        )
+    }
+
+    #[test]
+    fn it_does_not_move_when_the_world_matches_the_viewport() {
+        let mut camera = Camera::new(VIEWPORT, VIEWPORT);
+
+        camera.follow(&get_drawable_mock(Point::new(18, 8)));
+
+        assert_eq!(camera.position, Point::new(0, 0));
+        assert_eq!(camera.world_to_screen(Point::new(18, 8)), Point::new(18, 8));
+    }
+
+    #[test]
+    fn it_centers_on_the_followed_drawable() {
+        let mut camera = Camera::new(VIEWPORT, Point::new(100, 10));
+
+        camera.follow(&get_drawable_mock(Point::new(49, 4)));
+
+        // The drawable's center is at (50, 5), so it should be in the middle of the viewport
+        assert_eq!(camera.position, Point::new(40, 0));
+        assert_eq!(camera.world_to_screen(Point::new(50, 5)), Point::new(10, 5));
+    }
+
+    #[test]
+    fn it_converts_screen_locations_back_to_the_world() {
+        let mut camera = Camera::new(VIEWPORT, Point::new(100, 10));
+
+        camera.set_position(Point::new(30, 0)).shake(2);
+
+        let world_location = Point::new(42, 6);
+
+        assert_eq!(
+            camera.screen_to_world(camera.world_to_screen(world_location)),
+            world_location
+        );
+    }
+
+    #[test]
+    fn it_clamps_to_the_world_bounds() {
+        let mut camera = Camera::new(VIEWPORT, Point::new(100, 10));
+
+        camera.follow(&get_drawable_mock(Point::new(98, 4)));
+
+        assert_eq!(camera.position, Point::new(80, 0));
+
+        camera.follow(&get_drawable_mock(Point::new(0, 4)));
+
+        assert_eq!(camera.position, Point::new(0, 0));
+    }
+
+    #[test]
+    fn it_offsets_screen_locations_when_shaking() {
+        let mut camera = Camera::new(VIEWPORT, VIEWPORT);
+
+        camera.shake(2);
+
+        let screen_location = camera.world_to_screen(Point::new(10, 5));
+
+        assert!((screen_location.width - 10).abs() <= 2);
+        assert!((screen_location.height - 5).abs() <= 2);
+
+        camera.clear_shake();
+
+        assert_eq!(camera.world_to_screen(Point::new(10, 5)), Point::new(10, 5));
+    }
+}
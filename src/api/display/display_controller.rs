@@ -2,22 +2,25 @@ use crossterm::style::Color;
 use crossterm::terminal::size;
 use uuid::Uuid;
 
-use crate::app::GameState;
+use crate::app::{GameState, Menu};
 use crate::components::{Drawable, DrawableState, Health};
-use crate::helpers::{
-    get_is_position_outside_dimensions, get_is_position_outside_dimensions_with_offset,
-};
-use crate::systems::EntityController;
+use crate::entities::{PickupKind, Weapon, WeaponKind, WeaponLimit};
+use crate::helpers::{get_is_drawable_outside_dimensions, get_is_position_outside_dimensions};
+use crate::systems::{EntityController, ScreenEffects};
 use crate::user_display::{HEART, NUMBER_VECTOR};
 
-use super::element::{parse_str_to_element_array, DEFAULT_BACKGROUND, DEFAULT_FOREGROUND};
-use super::{create_map, map_from_str, Element, Map, Point};
+use super::element::{parse_str_to_element_array, BlendMode, DEFAULT_BACKGROUND};
+use super::{create_map, map_from_str, Camera, Element, Map, Point};
 use super::{display_controller_error::DisplayControllerError, Layout};
 
 pub struct DisplayController {
     entity_drawable_offset: Point<i64>,
     pub layout: Layout,
+    pub camera: Camera,
     numbers: Vec<Map>,
+    // Screen effect state for the current frame, set by apply_screen_effects
+    hidden_uuids: Vec<Uuid>,
+    flash_color: Option<Color>,
 }
 
 type DisplayControllerResult<T> = Result<T, DisplayControllerError>;
@@ -28,7 +31,60 @@ pub fn get_screen_size() -> Point<i64> {
     Point::new(rows as i64, columns as i64)
 }
 
-const LIFE_ELEMENT: Element = Element::new('♥', DEFAULT_BACKGROUND, Color::Red);
+/// Writes the text into a menu's map starting at the location, text that would reach the right border is cut off
+fn write_menu_text(map: &mut Map, text: &str, location: Point<i64>, color: Color) {
+    let Some(row) = map.get_mut(location.height as usize) else {
+        return;
+    };
+
+    // The last column is the border
+    let end = row.len().saturating_sub(1);
+
+    for (index, element) in parse_str_to_element_array(text, None, Some(color))
+        .into_iter()
+        .enumerate()
+    {
+        let width = location.width as usize + index;
+
+        if width >= end {
+            break;
+        }
+
+        if element.is_some() {
+            row[width] = element;
+        }
+    }
+}
+
+/// Rows between the top of a menu's box and its first item, this leaves room for the border and title
+const MENU_ITEMS_OFFSET: i64 = 3;
+/// Rows in a menu's box that aren't items, the border and title above the items and a blank row and the border below them
+const MENU_EXTRA_ROWS: i64 = MENU_ITEMS_OFFSET + 2;
+
+/// Where a menu's box is drawn on the screen and which of its items are shown
+struct MenuBounds {
+    start: Point<i64>,
+    dimensions: Point<i64>,
+    /// Index of the first item shown, menus with more items than fit in the viewport scroll to keep the selected item shown
+    first_item: usize,
+    item_count: usize,
+}
+
+/// Screen location of the current wave number, above the boss health bar
+const WAVE_LOCATION: Point<i64> = Point::new(40, 1);
+/// Screen location of the boss health bar's label, the bar follows the label
+const BOSS_HEALTH_BAR_LOCATION: Point<i64> = Point::new(40, 3);
+/// Number of cells in a full boss health bar
+const BOSS_HEALTH_BAR_WIDTH: i64 = 30;
+
+/// Screen location of the list of active power ups, below the lives
+const POWER_UPS_LOCATION: Point<i64> = Point::new(5, 8);
+/// Screen location of the list of weapons, below the boss health bar. Weapons are listed one per row so the HUD fits an 80 column terminal
+const WEAPONS_LOCATION: Point<i64> = Point::new(40, 5);
+
+/// The crosshair keeps the background so whatever is under the mouse stays visible
+const CROSSHAIR_ELEMENT: Element =
+    Element::new('+', DEFAULT_BACKGROUND, Color::Red).with_blend_mode(BlendMode::KeepBackground);
 
 impl DisplayController {
     /// Creates a new display controller, a display controller fills the entire screen but the provided dimensions will be the controllable area
@@ -36,11 +92,14 @@ impl DisplayController {
     /// # Arguments
     ///
     /// * `dimensions` - The controllable area, if None then the entire screen is used
+    /// * `entity_drawable_offset` - Where the viewport for entities starts, everything above and to the left of this is reserved for the user's UI
+    /// * `world_dimensions` - Dimensions of the world the camera moves within, use the viewport dimensions for a world that exactly fits the screen
     ///
     /// ```
     pub fn new(
         dimensions: Point<i64>,
         entity_drawable_offset: Point<i64>,
+        world_dimensions: Point<i64>,
     ) -> Result<Self, DisplayControllerError> {
         let mut numbers: Vec<Map> = vec![create_map(&Default::default(), None); 10];
 
@@ -52,10 +111,39 @@ impl DisplayController {
             layout: Layout::new(&dimensions, None),
             // The offset is where all drawing will be done, this is the center of the terminal screen
             entity_drawable_offset,
+            camera: Camera::new(dimensions - entity_drawable_offset, world_dimensions),
             numbers,
+            hidden_uuids: vec![],
+            flash_color: None,
         })
     }
 
+    /// Reads the current state of the screen effects, this should be called each frame before anything is drawn
+    pub fn apply_screen_effects(&mut self, screen_effects: &ScreenEffects) -> &mut Self {
+        self.camera.shake(screen_effects.get_shake_magnitude());
+
+        self.hidden_uuids = screen_effects.get_hidden_uuids();
+        self.flash_color = screen_effects.get_flash_color();
+
+        self
+    }
+
+    /// Tints the entire screen with the current flash color, this should be called after everything else has been drawn
+    pub fn draw_flash(&mut self) -> DisplayControllerResult<&mut Self> {
+        if let Some(flash_color) = self.flash_color {
+            let tint = Element::new_tint(flash_color, Color::Black);
+
+            for height in 0..self.layout.dimensions.height {
+                for width in 0..self.layout.dimensions.width {
+                    self.layout.draw_item(tint, &Point::new(width, height))?;
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Draws the lives and the current wave in the HUD
     pub fn draw_game_state(
         &mut self,
         game_state: &GameState,
@@ -72,30 +160,302 @@ impl DisplayController {
             &Default::default(),
         )?;
 
-        // self.layout
-        //     .draw_str(HEART, &Point::new(5, 2), None, Some(Color::Red))?;
+        self.layout.draw_str(
+            &format!("WAVE {}", game_state.wave),
+            &WAVE_LOCATION,
+            None,
+            Some(Color::White),
+        )?;
+
+        Ok(self)
+    }
+
+    /// Draws the player's active power ups in the HUD, timed power ups show their remaining seconds
+    pub fn draw_power_ups(
+        &mut self,
+        power_ups: &[(PickupKind, Option<u128>)],
+    ) -> DisplayControllerResult<&mut Self> {
+        let text = power_ups
+            .iter()
+            .map(|(kind, time_remaining)| match time_remaining {
+                // Round up so the effect doesn't show 0s while it is still active
+                Some(time_remaining) => {
+                    format!("{} {}s", kind.get_label(), time_remaining.div_ceil(1000))
+                }
+                None => kind.get_label().to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join("  ");
+
+        if !text.is_empty() {
+            self.layout
+                .draw_str(&text, &POWER_UPS_LOCATION, None, Some(Color::Green))?;
+        }
+
+        Ok(self)
+    }
 
-        // let life_elements = vec![Some(LIFE_ELEMENT); lives as usize];
+    /// Draws the player's weapons in the HUD with their number key, level and ammo, heat or energy, the selected weapon is highlighted
+    pub fn draw_weapons(
+        &mut self,
+        weapons: &[Weapon],
+        selected: WeaponKind,
+    ) -> DisplayControllerResult<&mut Self> {
+        for (index, weapon) in weapons.iter().enumerate() {
+            let limit = match weapon.limit {
+                WeaponLimit::None => String::new(),
+                WeaponLimit::Ammo(_) => format!(" x{}", weapon.ammo),
+                WeaponLimit::Heat { .. } if weapon.is_locked_out() => " HOT".to_string(),
+                WeaponLimit::Heat { .. } => format!(" {}%", weapon.heat.round()),
+                WeaponLimit::Energy { .. } if weapon.is_locked_out() => " EMPTY".to_string(),
+                WeaponLimit::Energy { .. } => format!(" {}%", weapon.energy.round()),
+            };
+
+            let text = format!(
+                "{} {} L{}{}",
+                index + 1,
+                weapon.kind.get_label(),
+                weapon.level,
+                limit
+            );
+
+            self.layout.draw_str(
+                &text,
+                &WEAPONS_LOCATION.add_height(index as i64),
+                None,
+                Some(if weapon.kind == selected {
+                    Color::Yellow
+                } else {
+                    Color::Grey
+                }),
+            )?;
+        }
 
-        // self.layout.draw_element_array(life_elements)?;
+        Ok(self)
+    }
 
-        // Convert the score string into an array of elements for simple printing to the display
-        // let score_array = parse_str_to_element_array(&game_state.score.to_string(), None, None);
+    /// Draws the boss's health as a bar in the HUD
+    pub fn draw_boss_health(
+        &mut self,
+        health: u32,
+        max_health: u32,
+    ) -> DisplayControllerResult<&mut Self> {
+        let filled = (BOSS_HEALTH_BAR_WIDTH * health as i64) / max_health.max(1) as i64;
+
+        self.layout
+            .draw_str("BOSS", &BOSS_HEALTH_BAR_LOCATION, None, Some(Color::White))?;
+
+        let bar_location = BOSS_HEALTH_BAR_LOCATION.add_width(5);
 
-        // self.layout.draw_element_array(
-        //     score_array,
-        //     &Point::new(self.layout.dimensions.width - 5, 2),
-        // )?;
+        self.layout.draw_str(
+            &"#".repeat(filled as usize),
+            &bar_location,
+            None,
+            Some(Color::Red),
+        )?;
+        self.layout.draw_str(
+            &"-".repeat((BOSS_HEALTH_BAR_WIDTH - filled) as usize),
+            &bar_location.add_width(filled),
+            None,
+            Some(Color::DarkGrey),
+        )?;
 
         Ok(self)
     }
 
-    /// This method handles drawing drawable elements, it also skips over the drawing of an element if it is outside the range
+    /// Returns where the menu's box is drawn and which items it shows. The box is centered in the viewport and shrunk to fit it, when not every item fits the items scroll with the selection
+    fn get_menu_bounds(&self, menu: &Menu) -> MenuBounds {
+        let viewport = self.camera.viewport;
+
+        // Space for the selection marker on each item and padding either side of the text
+        let content_width = menu
+            .items
+            .iter()
+            .map(|item| item.len() as i64 + 2)
+            .chain([menu.title.len() as i64])
+            .max()
+            .unwrap_or(0);
+
+        let item_count = (menu.items.len() as i64)
+            .min(viewport.height - MENU_EXTRA_ROWS)
+            .max(1) as usize;
+
+        // Scroll just far enough for the selected item to be the last one shown
+        let first_item = (menu.selected + 1).saturating_sub(item_count);
+
+        let dimensions = Point::new(
+            (content_width + 4).min(viewport.width),
+            item_count as i64 + MENU_EXTRA_ROWS,
+        );
+
+        let start = self.entity_drawable_offset
+            + Point::new(
+                ((viewport.width - dimensions.width) / 2).max(0),
+                ((viewport.height - dimensions.height) / 2).max(0),
+            );
+
+        MenuBounds {
+            start,
+            dimensions,
+            first_item,
+            item_count,
+        }
+    }
+
+    /// Returns the index of the menu item drawn on the row of the screen location, used to select items with the mouse
+    pub fn get_menu_item_at(&self, menu: &Menu, screen_location: &Point<i64>) -> Option<usize> {
+        let MenuBounds {
+            start,
+            dimensions,
+            first_item,
+            item_count,
+        } = self.get_menu_bounds(menu);
+
+        // The border columns aren't part of any item
+        if screen_location.width <= start.width
+            || screen_location.width >= start.width + dimensions.width - 1
+        {
+            return None;
+        }
+
+        let row = screen_location.height - start.height - MENU_ITEMS_OFFSET;
+
+        if row < 0 || row >= item_count as i64 {
+            return None;
+        }
+
+        Some(first_item + row as usize)
+    }
+
+    /// Converts a location on the screen into a world location, None if the location is outside the viewport such as in the HUD
+    pub fn get_world_location(&self, screen_location: &Point<i64>) -> Option<Point<i64>> {
+        let viewport_location = *screen_location - self.entity_drawable_offset;
+
+        if get_is_position_outside_dimensions(&self.camera.viewport, &viewport_location) {
+            return None;
+        }
+
+        Some(self.camera.screen_to_world(viewport_location))
+    }
+
+    /// Draws the crosshair at the screen location, nothing is drawn if the location is outside the viewport
+    pub fn draw_crosshair(
+        &mut self,
+        screen_location: &Point<i64>,
+    ) -> DisplayControllerResult<&mut Self> {
+        if self.get_world_location(screen_location).is_some() {
+            self.layout.draw_item(CROSSHAIR_ELEMENT, screen_location)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Draws a line of text in the center of the viewport, used to announce waves
+    pub fn draw_banner(&mut self, text: &str) -> DisplayControllerResult<&mut Self> {
+        let viewport = self.camera.viewport;
+        let location = self.entity_drawable_offset
+            + Point::new(
+                (viewport.width - text.len() as i64) / 2,
+                viewport.height / 2,
+            );
+
+        self.layout
+            .draw_str(text, &location, None, Some(Color::Yellow))?;
+
+        Ok(self)
+    }
+
+    /// Draws the menu in a box in the center of the viewport, the selected item is highlighted
+    pub fn draw_menu(&mut self, menu: &Menu) -> DisplayControllerResult<&mut Self> {
+        let MenuBounds {
+            start,
+            dimensions,
+            first_item,
+            item_count,
+        } = self.get_menu_bounds(menu);
+
+        // The box is drawn into its own map first so anything that doesn't fit on the screen is clipped like a drawable rather than failing to draw
+        let mut map = create_map(
+            &dimensions,
+            Some(Element::new(' ', DEFAULT_BACKGROUND, Color::White)),
+        );
+
+        let border = Element::new('#', DEFAULT_BACKGROUND, Color::White);
+
+        for (height, row) in map.iter_mut().enumerate() {
+            let is_edge_row = height == 0 || height as i64 == dimensions.height - 1;
+
+            for (width, element) in row.iter_mut().enumerate() {
+                if is_edge_row || width == 0 || width as i64 == dimensions.width - 1 {
+                    *element = Some(border);
+                }
+            }
+        }
+
+        write_menu_text(
+            &mut map,
+            &menu.title,
+            Point::new(((dimensions.width - menu.title.len() as i64) / 2).max(1), 1),
+            Color::White,
+        );
+
+        for (row, (index, item)) in menu
+            .items
+            .iter()
+            .enumerate()
+            .skip(first_item)
+            .take(item_count)
+            .enumerate()
+        {
+            let is_selected = index == menu.selected;
+
+            write_menu_text(
+                &mut map,
+                &format!("{} {}", if is_selected { ">" } else { " " }, item),
+                Point::new(2, MENU_ITEMS_OFFSET + row as i64),
+                if is_selected {
+                    Color::Yellow
+                } else {
+                    Color::Grey
+                },
+            );
+        }
+
+        self.layout
+            .draw_map(&map, start, &self.entity_drawable_offset)?;
+
+        Ok(self)
+    }
+
+    /// This method handles drawing drawable elements, it also skips over the drawing of an element if it is outside the range. The drawable's location is in world coordinates and is converted to the viewport through the camera
     pub fn draw_drawable(
         &mut self,
         drawable_state: &DrawableState,
     ) -> DisplayControllerResult<(&mut Self, bool)> {
-        let base_location = drawable_state.location + self.entity_drawable_offset;
+        let screen_location = self.camera.world_to_screen(drawable_state.get_location());
+
+        self.draw_drawable_at(drawable_state, screen_location)
+    }
+
+    /// Draws a drawable whose location is relative to the viewport rather than the world, this is used for backgrounds that stay fixed to the screen
+    pub fn draw_screen_drawable(
+        &mut self,
+        drawable_state: &DrawableState,
+    ) -> DisplayControllerResult<(&mut Self, bool)> {
+        self.draw_drawable_at(drawable_state, drawable_state.get_location())
+    }
+
+    fn draw_drawable_at(
+        &mut self,
+        drawable_state: &DrawableState,
+        screen_location: Point<i64>,
+    ) -> DisplayControllerResult<(&mut Self, bool)> {
+        // Blinking drawables are skipped entirely while they are blinked out
+        if self.hidden_uuids.contains(&drawable_state.uuid) {
+            return Ok((self, false));
+        }
+
+        let base_location = screen_location + self.entity_drawable_offset;
 
         let has_drawn_drawable = self.layout.draw_map(
             &drawable_state.layout.map,
@@ -106,7 +466,7 @@ impl DisplayController {
         Ok((self, has_drawn_drawable))
     }
 
-    /// Draws the entities within a given entity controller. It also removes items from the entities array if they are outside of the drawable dimensions.
+    /// Draws the entities within a given entity controller. It also removes items from the entities array if they are outside of the world dimensions.
     /// This is primarily used for the Bullet controller and asteroid controller
     pub fn draw_entity_controller_items<T: Drawable + Health>(
         &mut self,
@@ -116,13 +476,18 @@ impl DisplayController {
         let mut delete_uuids: Vec<Uuid> = vec![];
 
         for entity in entity_controller.get_all_drawable_states() {
-            let result = self.draw_drawable(entity);
-
-            let (_, did_draw) = result.unwrap();
-
-            if !did_draw {
+            // Entities outside the viewport are still alive as long as they are within the world
+            if get_is_drawable_outside_dimensions(
+                &self.camera.world_dimensions,
+                &entity.get_location(),
+                &entity.layout.dimensions,
+            ) {
                 delete_uuids.push(entity.uuid);
+
+                continue;
             }
+
+            self.draw_drawable(entity).unwrap();
         }
 
         for uuid in delete_uuids {
@@ -137,14 +502,13 @@ impl DisplayController {
 mod tests {
     use crate::{
         api::display::{display_controller::get_screen_size, Element, Layout, Point},
+        app::{GameState, Menu},
         components::{Drawable, DrawableState, DrawableType},
+        entities::{PickupKind, Weapon, WeaponKind},
+        systems::{Action, KeyMap},
     };
 
-    use super::DisplayController;
-
-    const WIDTH: i64 = 30;
-    const HEIGHT: i64 = 20;
-    const DIMENSIONS: &Point<i64> = &Point::new(WIDTH, HEIGHT);
+    use super::{DisplayController, WAVE_LOCATION};
 
     struct MockDrawble {
         drawable_state: DrawableState,
@@ -164,7 +528,7 @@ mod tests {
     }
 
     impl Drawable for MockDrawble {
-        fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
+        fn set_position(&mut self, _updated_position: Point<f64>) -> &mut Self {
             todo!()
         }
 
@@ -173,14 +537,161 @@ mod tests {
         }
     }
 
+    #[test]
+    fn it_finds_the_menu_item_under_a_screen_location() {
+        let display_controller =
+            DisplayController::new(Point::new(40, 30), Point::new(0, 10), Point::new(40, 20))
+                .unwrap();
+
+        let menu = Menu::new("Menu", vec!["First".to_string(), "Second".to_string()]);
+
+        // The menu box is 12x7 and centered in the 40x20 viewport below the HUD, so it starts at (14, 16)
+        assert_eq!(
+            display_controller.get_menu_item_at(&menu, &Point::new(18, 19)),
+            Some(0)
+        );
+        assert_eq!(
+            display_controller.get_menu_item_at(&menu, &Point::new(18, 20)),
+            Some(1)
+        );
+        // Title row and border column
+        assert_eq!(
+            display_controller.get_menu_item_at(&menu, &Point::new(18, 17)),
+            None
+        );
+        assert_eq!(
+            display_controller.get_menu_item_at(&menu, &Point::new(14, 19)),
+            None
+        );
+    }
+
     #[test]
     fn it_can_handle_drawable_outside_dimensions() {
         // let drawable = Drawable::
         let mut display_controller =
-            DisplayController::new(get_screen_size(), Default::default()).unwrap();
+            DisplayController::new(get_screen_size(), Default::default(), get_screen_size())
+                .unwrap();
 
-        let result = display_controller.draw_drawable(&MockDrawble::new().get_drawable_state());
+        let result = display_controller.draw_drawable(MockDrawble::new().get_drawable_state());
 
         assert!(result.err().is_none());
     }
+
+    #[test]
+    fn it_fits_the_hud_in_an_80_column_terminal() {
+        let mut display_controller =
+            DisplayController::new(Point::new(80, 30), Point::new(0, 10), Point::new(80, 20))
+                .unwrap();
+
+        // Every weapon at its widest, with the laser out of energy
+        let mut weapons: Vec<Weapon> = WeaponKind::ALL.into_iter().map(Weapon::new).collect();
+
+        for weapon in weapons.iter_mut() {
+            while weapon.upgrade() {}
+
+            while weapon.drain_energy(1000) {}
+        }
+
+        let power_ups = vec![
+            (PickupKind::Shield, Some(10000)),
+            (PickupKind::RapidFire, Some(8000)),
+            (PickupKind::Spread, None),
+        ];
+
+        display_controller
+            .draw_game_state(&GameState::new(), 9)
+            .unwrap()
+            .draw_power_ups(&power_ups)
+            .unwrap()
+            .draw_weapons(&weapons, WeaponKind::Laser)
+            .unwrap()
+            .draw_boss_health(10, 30)
+            .unwrap();
+    }
+
+    #[test]
+    fn it_draws_the_wave_in_the_hud() {
+        let mut display_controller =
+            DisplayController::new(Point::new(80, 30), Point::new(0, 10), Point::new(80, 20))
+                .unwrap();
+
+        let mut game_state = GameState::new();
+
+        game_state.wave = 12;
+
+        display_controller.draw_game_state(&game_state, 3).unwrap();
+
+        let text: String = (0..7)
+            .map(|width| {
+                display_controller
+                    .layout
+                    .get_element(&WAVE_LOCATION.add_width(width))
+                    .unwrap()
+                    .map_or(' ', |element| element.value)
+            })
+            .collect();
+
+        assert_eq!(text, "WAVE 12");
+    }
+
+    #[test]
+    fn it_scrolls_the_controls_menu_to_fit_an_80_by_24_terminal() {
+        let mut display_controller =
+            DisplayController::new(Point::new(80, 24), Point::new(0, 10), Point::new(80, 14))
+                .unwrap();
+
+        let key_map = KeyMap::default();
+
+        let mut menu = Menu::new(
+            "Controls",
+            Action::ALL
+                .iter()
+                .map(|action| {
+                    format!(
+                        "{}: {}",
+                        action.get_label(),
+                        key_map.get_keys_label(*action)
+                    )
+                })
+                .chain(["Back".to_string()])
+                .collect(),
+        );
+
+        // Every item can be selected and drawn, the list scrolls to keep the selection in the box
+        for index in 0..menu.items.len() {
+            menu.selected = index;
+
+            display_controller.draw_menu(&menu).unwrap();
+
+            let item_location = (0..24).map(|row| Point::new(40, row)).find(|location| {
+                display_controller.get_menu_item_at(&menu, location) == Some(index)
+            });
+
+            assert!(item_location.is_some(), "item {} isn't shown", index);
+        }
+    }
+
+    #[test]
+    fn it_clips_menus_that_dont_fit_the_screen() {
+        let mut display_controller =
+            DisplayController::new(Point::new(12, 14), Point::new(0, 10), Point::new(12, 4))
+                .unwrap();
+
+        let menu = Menu::new(
+            "A title wider than the screen",
+            vec![
+                "An item wider than the screen".to_string(),
+                "Back".to_string(),
+            ],
+        );
+
+        display_controller.draw_menu(&menu).unwrap();
+
+        // The HUD above the viewport isn't drawn over
+        assert!((0..12).all(|width| display_controller
+            .layout
+            .get_element(&Point::new(width, 9))
+            .unwrap()
+            .is_none()));
+    }
 }
@@ -10,7 +10,7 @@ pub enum DisplayControllerError {
 }
 
 impl DisplayControllerError {
-    pub fn from_crossterm_error(error: ErrorKind) -> Self {
+    pub fn from_crossterm_error(_error: ErrorKind) -> Self {
         Self::CrossTermWriteError
     }
 }
@@ -3,11 +3,23 @@ use crossterm::style::Color;
 pub const DEFAULT_BACKGROUND: Color = Color::Black;
 pub const DEFAULT_FOREGROUND: Color = Color::Blue;
 
+/// Determines how an element is combined with the element that already occupies its cell when it is drawn
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BlendMode {
+    /// Overwrites the entire cell, including the background colour
+    Replace,
+    /// Draws the character and foreground but treats the background as transparent, so the background of the cell underneath is kept. Sprites use this so they don't erase the starfield or effects behind them
+    KeepBackground,
+    /// Treats the character as transparent, keeping the character underneath and adding this element's colours onto the cell. Black adds nothing, so a tint that only changes the background can use a black foreground
+    Additive,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Element {
     pub value: char,
     pub background: Color,
     pub foreground: Color,
+    pub blend_mode: BlendMode,
 }
 
 impl Default for Element {
@@ -16,6 +28,7 @@ impl Default for Element {
             value: ' ',
             background: DEFAULT_BACKGROUND,
             foreground: DEFAULT_FOREGROUND,
+            blend_mode: BlendMode::Replace,
         }
     }
 }
@@ -45,6 +58,7 @@ impl Element {
             value,
             background,
             foreground,
+            blend_mode: BlendMode::Replace,
         }
     }
 
@@ -54,20 +68,123 @@ impl Element {
             value: ' ',
             background: DEFAULT_BACKGROUND,
             foreground: DEFAULT_FOREGROUND,
+            blend_mode: BlendMode::Replace,
         }
     }
 
-    pub const fn new_default_colors(value: char) -> Self {
+    /// Creates an additive element that tints the cell underneath without changing its character
+    pub const fn new_tint(background: Color, foreground: Color) -> Self {
         Self {
-            value,
-            background: DEFAULT_BACKGROUND,
-            foreground: DEFAULT_FOREGROUND,
+            value: ' ',
+            background,
+            foreground,
+            blend_mode: BlendMode::Additive,
+        }
+    }
+
+    pub const fn with_blend_mode(self, blend_mode: BlendMode) -> Self {
+        Self { blend_mode, ..self }
+    }
+
+    /// Combines this element with the element currently occupying the cell, returning the element that should be stored in the cell
+    ///
+    /// # Arguments
+    ///
+    /// * `existing` - The element already in the cell, None is treated as the default element
+    pub fn blend(self, existing: Option<Element>) -> Element {
+        let existing = existing.unwrap_or(Element::default());
+
+        match self.blend_mode {
+            BlendMode::Replace => self,
+            BlendMode::KeepBackground => Element {
+                background: existing.background,
+                blend_mode: BlendMode::Replace,
+                ..self
+            },
+            BlendMode::Additive => Element {
+                value: existing.value,
+                background: add_colors(existing.background, self.background),
+                foreground: add_colors(existing.foreground, self.foreground),
+                blend_mode: BlendMode::Replace,
+            },
+        }
+    }
+}
+
+const ANSI_RGB: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (128, 0, 0),
+    (0, 128, 0),
+    (128, 128, 0),
+    (0, 0, 128),
+    (128, 0, 128),
+    (0, 128, 128),
+    (192, 192, 192),
+    (128, 128, 128),
+    (255, 0, 0),
+    (0, 255, 0),
+    (255, 255, 0),
+    (0, 0, 255),
+    (255, 0, 255),
+    (0, 255, 255),
+    (255, 255, 255),
+];
+
+/// Converts a terminal color into an approximate rgb value, returns None for Color::Reset as it depends on the terminal
+pub fn color_to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    let ansi_index = match color {
+        Color::Reset => return None,
+        Color::Rgb { r, g, b } => return Some((r, g, b)),
+        Color::Black => 0,
+        Color::DarkRed => 1,
+        Color::DarkGreen => 2,
+        Color::DarkYellow => 3,
+        Color::DarkBlue => 4,
+        Color::DarkMagenta => 5,
+        Color::DarkCyan => 6,
+        Color::Grey => 7,
+        Color::DarkGrey => 8,
+        Color::Red => 9,
+        Color::Green => 10,
+        Color::Yellow => 11,
+        Color::Blue => 12,
+        Color::Magenta => 13,
+        Color::Cyan => 14,
+        Color::White => 15,
+        Color::AnsiValue(value) => value,
+    };
+
+    match ansi_index {
+        0..=15 => Some(ANSI_RGB[ansi_index as usize]),
+        // The 6x6x6 color cube
+        16..=231 => {
+            let cube_index = ansi_index - 16;
+            let scale = |value: u8| if value == 0 { 0 } else { 55 + value * 40 };
+
+            Some((
+                scale(cube_index / 36),
+                scale((cube_index / 6) % 6),
+                scale(cube_index % 6),
+            ))
+        }
+        // The grayscale ramp
+        _ => {
+            let gray = 8 + (ansi_index - 232) * 10;
+
+            Some((gray, gray, gray))
         }
     }
 }
 
-impl From<u32> for Element {
-    fn from(_: u32) -> Self {
-        todo!();
+/// Adds two colors together channel by channel, saturating at white
+pub fn add_colors(base: Color, tint: Color) -> Color {
+    match (color_to_rgb(base), color_to_rgb(tint)) {
+        (Some(base), Some(tint)) => Color::Rgb {
+            r: base.0.saturating_add(tint.0),
+            g: base.1.saturating_add(tint.1),
+            b: base.2.saturating_add(tint.2),
+        },
+        (None, _) => tint,
+        (_, None) => base,
     }
 }
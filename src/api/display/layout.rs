@@ -3,17 +3,18 @@ use crossterm::style::Color;
 use crate::helpers::get_is_position_outside_dimensions_with_offset;
 
 use super::{
-    element::{parse_str_to_element_array, Element, DEFAULT_BACKGROUND},
+    element::{parse_str_to_element_array, Element},
     map::{create_map, map_from_str, Map},
     DisplayControllerError, Point,
 };
 
 pub type TwoDVec<T> = Vec<Vec<T>>;
 
-pub fn collapse_twoDVec<T>(twoDVec: TwoDVec<T>) -> Vec<T> {
+#[cfg(test)]
+pub fn collapse_two_d_vec<T>(two_d_vec: TwoDVec<T>) -> Vec<T> {
     let mut return_vec: Vec<T> = vec![];
 
-    for row in twoDVec {
+    for row in two_d_vec {
         for item in row {
             return_vec.push(item);
         }
@@ -70,56 +71,7 @@ impl Layout {
         Layout::from_map(map_from_str(ascii, color), None)
     }
 
-    pub fn from_file(path: &str) -> Self {
-        let img = image::open(path).unwrap();
-        let rgb_img = img.to_rgb8();
-
-        let (width, height) = (img.width(), img.height());
-
-        let raw_rgb = rgb_img.to_vec();
-
-        dbg!(width);
-        dbg!(height);
-
-        // Use a 1D vector before converting to the 2D vector to simplify the logic
-        let mut converted_items: Vec<Option<Element>> = vec![None; (width * height) as usize];
-
-        for index in (0..converted_items.len()) {
-            let base_index = index * 3;
-            let (r, g, b) = (
-                raw_rgb[base_index],
-                raw_rgb[base_index + 1],
-                raw_rgb[base_index + 2],
-            );
-
-            converted_items[index] = Some(Element::new(
-                ' ',
-                DEFAULT_BACKGROUND,
-                Color::Rgb { r, g, b },
-            ));
-        }
-
-        let mut map = vec![vec![None; width as usize]; height as usize];
-
-        for index in 0..converted_items.len() as i64 {
-            let height = index / width as i64;
-            let width = index % width as i64;
-
-            map[height as usize][width as usize] = converted_items[index as usize];
-        }
-
-        // for index in (0..raw_rgb.len()).step_by(3) {
-        //
-
-        //
-
-        //     // map[]
-        // }
-
-        Layout::from_map(map, None)
-    }
-
-    /// This method allows drawing an additional map ontop of the map contained within this layout. This is useful when drawing ascii art.
+    /// This method allows drawing an additional map ontop of the map contained within this layout. This is useful when drawing ascii art. Each element is blended with the cell underneath according to its blend mode.
     /// # Arguments
     ///
     /// * `map` - The map to draw
@@ -136,8 +88,8 @@ impl Layout {
         // Iterate over each row in the map
         for (num_row, drawable_row) in map.iter().enumerate() {
             // Then each column in the row
-            for num_column in 0..drawable_row.len() {
-                if let Some(has_element) = drawable_row[num_column] {
+            for (num_column, element) in drawable_row.iter().enumerate() {
+                if let Some(has_element) = *element {
                     let updated_position = location
                         .add_width(num_column as i64)
                         .add_height(num_row as i64);
@@ -183,6 +135,7 @@ impl Layout {
 
     // pub fn get_vec_of_all_points_with_element(&self)
 
+    #[cfg(test)]
     pub fn get_row(&self, row_number: i64) -> LayoutResult<&Vec<Option<Element>>> {
         let row = self
             .map
@@ -193,6 +146,7 @@ impl Layout {
     }
 
     /// Returns the selected column_number. Has a differing type to get_row because we have to create an array of references and return it, whereas the get_row method returns a pointer to the row. A column doesn't exactly exist, it is just an element at the same row index for each row
+    #[cfg(test)]
     pub fn get_column(&self, column_number: i64) -> LayoutResult<Vec<&Option<Element>>> {
         let mut items: Vec<&Option<Element>> = Vec::with_capacity(self.dimensions.height as usize);
 
@@ -240,6 +194,7 @@ impl Layout {
         Ok(element)
     }
 
+    #[cfg(test)]
     pub fn get_element(&self, point: &Point<i64>) -> LayoutResult<&Option<Element>> {
         let row = self.get_row(point.height)?;
 
@@ -296,8 +251,9 @@ impl Layout {
             Direction::Horizontal => {
                 let row = self.get_row_mut(start_position.height)?;
 
-                for index in 0..len {
-                    row[index as usize] = Some(element);
+                for index in start_position.width..start_position.width + len {
+                    *row.get_mut(index as usize)
+                        .ok_or(DisplayControllerError::PositionOutOfRange)? = Some(element);
                 }
             }
             Direction::Vertical => {
@@ -339,9 +295,9 @@ impl Layout {
             return Err(DisplayControllerError::PositionOutOfRange);
         }
 
-        let existing_element = self.get_element_mut(&position)?;
+        let existing_element = self.get_element_mut(position)?;
 
-        *existing_element = Some(element);
+        *existing_element = Some(element.blend(*existing_element));
 
         Ok(self)
     }
@@ -349,17 +305,20 @@ impl Layout {
 
 #[cfg(test)]
 mod tests {
-    use crate::api::display::{Element, Layout, Point};
+    use crossterm::style::Color;
 
-    use super::Direction;
+    use crate::api::display::{
+        element::{BlendMode, DEFAULT_BACKGROUND, DEFAULT_FOREGROUND},
+        Element, Layout, Point,
+    };
+
+    use super::{collapse_two_d_vec, Direction};
 
     const WIDTH: i64 = 30;
     const HEIGHT: i64 = 20;
 
     const DIMENSIONS: &Point<i64> = &Point::new(WIDTH, HEIGHT);
 
-    const DEFAULT_ELEMENT: Element = Element::default();
-
     #[test]
     fn it_creates_correct_dimensions() {
         let map = Layout::new(DIMENSIONS, None);
@@ -373,7 +332,7 @@ mod tests {
         }
     }
 
-    const LINE_ELEMENT: Element = Element::new_default_colors('x');
+    const LINE_ELEMENT: Element = Element::new('x', DEFAULT_BACKGROUND, DEFAULT_FOREGROUND);
 
     #[test]
     fn it_draws_a_horizontal_line() {
@@ -439,4 +398,74 @@ mod tests {
 
         assert_eq!(right_column, vec![&Some(LINE_ELEMENT); HEIGHT as usize]);
     }
+
+    const BACKGROUND_ELEMENT: Element = Element::new('.', Color::DarkBlue, Color::White);
+
+    #[test]
+    fn it_replaces_the_cell_by_default() {
+        let mut map = Layout::new(DIMENSIONS, Some(BACKGROUND_ELEMENT));
+
+        map.draw_item(LINE_ELEMENT, &Point::default()).unwrap();
+
+        assert_eq!(
+            map.get_element(&Point::default()).unwrap(),
+            &Some(LINE_ELEMENT)
+        );
+    }
+
+    #[test]
+    fn it_keeps_the_background_for_transparent_elements() {
+        let mut map = Layout::new(DIMENSIONS, Some(BACKGROUND_ELEMENT));
+
+        map.draw_item(
+            LINE_ELEMENT.with_blend_mode(BlendMode::KeepBackground),
+            &Point::default(),
+        )
+        .unwrap();
+
+        let element = map.get_element(&Point::default()).unwrap().unwrap();
+
+        assert_eq!(element.value, LINE_ELEMENT.value);
+        assert_eq!(element.foreground, LINE_ELEMENT.foreground);
+        assert_eq!(element.background, BACKGROUND_ELEMENT.background);
+    }
+
+    #[test]
+    fn it_adds_colors_and_keeps_the_character_for_additive_elements() {
+        let mut map = Layout::new(DIMENSIONS, Some(BACKGROUND_ELEMENT));
+
+        map.draw_item(
+            Element::new_tint(Color::Rgb { r: 200, g: 0, b: 0 }, Color::Black),
+            &Point::default(),
+        )
+        .unwrap();
+
+        let element = map.get_element(&Point::default()).unwrap().unwrap();
+
+        assert_eq!(element.value, BACKGROUND_ELEMENT.value);
+        assert_eq!(
+            element.background,
+            Color::Rgb {
+                r: 200,
+                g: 0,
+                b: 128
+            }
+        );
+        assert_eq!(
+            element.foreground,
+            Color::Rgb {
+                r: 255,
+                g: 255,
+                b: 255
+            }
+        );
+    }
+
+    #[test]
+    fn it_collapses_rows_in_order() {
+        assert_eq!(
+            collapse_two_d_vec(vec![vec![1, 2], vec![], vec![3]]),
+            vec![1, 2, 3]
+        );
+    }
 }
@@ -1,6 +1,9 @@
 use crossterm::style::Color;
 
-use super::{element::parse_str_to_element_array, Element, Point, TwoDVec};
+use super::{
+    element::{parse_str_to_element_array, BlendMode},
+    Element, Point, TwoDVec,
+};
 
 pub type Map = TwoDVec<Option<Element>>;
 
@@ -8,16 +11,21 @@ pub fn create_map<T: Clone>(dimensions: &Point<i64>, default_item: T) -> Vec<Vec
     vec![vec![default_item; dimensions.width as usize]; dimensions.height as usize]
 }
 
+/// Parses ascii art into a map, the resulting elements keep the background of whatever they are drawn over
 pub fn map_from_str(str: &str, color: Color) -> Map {
     let rows = str.split("\n");
 
     let width = rows.into_iter().max_by_key(|row| row.len()).unwrap().len();
 
-    let mut map: Vec<Vec<Option<Element>>> =
-        vec![vec![None; width as usize]; str.split("\n").count()];
+    let mut map: Vec<Vec<Option<Element>>> = vec![vec![None; width]; str.split("\n").count()];
 
     for (index, row) in str.split("\n").enumerate() {
-        map[index] = parse_str_to_element_array(row, None, Some(color));
+        map[index] = parse_str_to_element_array(row, None, Some(color))
+            .into_iter()
+            .map(|element| {
+                element.map(|element| element.with_blend_mode(BlendMode::KeepBackground))
+            })
+            .collect();
     }
 
     map
@@ -1,3 +1,4 @@
+mod camera;
 mod display_controller;
 mod display_controller_error;
 pub mod element;
@@ -6,6 +7,7 @@ mod map;
 mod output;
 mod point;
 
+pub use camera::Camera;
 pub use display_controller::*;
 pub use display_controller_error::DisplayControllerError;
 pub use element::Element;
@@ -1,12 +1,14 @@
 use crossterm::{
     cursor::{Hide, MoveTo, Show},
+    event::{
+        DisableMouseCapture, EnableMouseCapture, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
     execute, queue,
     style::{Print, SetBackgroundColor, SetForegroundColor},
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
-    ErrorKind as CrosstermError,
 };
 use std::io;
-use std::io::Write;
 
 use crate::api::display::element::{DEFAULT_BACKGROUND, DEFAULT_FOREGROUND};
 
@@ -14,34 +16,60 @@ use super::{DisplayControllerError, Element, Layout, Point};
 
 pub struct Output {
     target: io::Stdout,
+    // Whether the keyboard enhancement flags were pushed in start, so close only pops them if they were
+    keyboard_enhancement: bool,
 }
 
 impl Output {
     pub fn new(target: io::Stdout) -> Self {
-        Output { target }
+        Output {
+            target,
+            keyboard_enhancement: false,
+        }
     }
 
-    pub fn start(&mut self) -> Result<(), DisplayControllerError> {
+    /// Prepares the terminal for drawing the game
+    ///
+    /// # Arguments
+    ///
+    /// * `mouse_capture` - When true the terminal reports mouse clicks and movement as events, this stops the user selecting text in the terminal while the game is running
+    pub fn start(&mut self, mouse_capture: bool) -> Result<(), DisplayControllerError> {
         enable_raw_mode().map_err(DisplayControllerError::from_crossterm_error)?;
 
         queue!(self.target, EnterAlternateScreen, Hide).unwrap();
 
+        // Ask the terminal to report key release events so held keys can be tracked accurately. Consoles without the keyboard protocol, like the legacy Windows console, return an error instead, the input state then falls back to timeouts
+        self.keyboard_enhancement = queue!(
+            self.target,
+            PushKeyboardEnhancementFlags(
+                KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | KeyboardEnhancementFlags::REPORT_EVENT_TYPES
+            )
+        )
+        .is_ok();
+
+        if mouse_capture {
+            queue!(self.target, EnableMouseCapture)
+                .map_err(DisplayControllerError::from_crossterm_error)?;
+        }
+
         Ok(())
     }
 
     pub fn close(&mut self) -> Result<(), DisplayControllerError> {
         disable_raw_mode().map_err(DisplayControllerError::from_crossterm_error)?;
-        execute!(self.target, LeaveAlternateScreen, Show)
-            .map_err(DisplayControllerError::from_crossterm_error)?;
 
-        Ok(())
-    }
+        if self.keyboard_enhancement {
+            queue!(self.target, PopKeyboardEnhancementFlags)
+                .map_err(DisplayControllerError::from_crossterm_error)?;
 
-    /// Flushing the target publishes all queued writes
-    fn flush(&mut self) -> &mut Self {
-        self.target.flush().unwrap();
+            self.keyboard_enhancement = false;
+        }
 
-        self
+        execute!(self.target, DisableMouseCapture, LeaveAlternateScreen, Show)
+            .map_err(DisplayControllerError::from_crossterm_error)?;
+
+        Ok(())
     }
 
     pub fn reset_cursor(&mut self) -> Result<(), DisplayControllerError> {
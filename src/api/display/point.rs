@@ -1,6 +1,6 @@
 use std::ops::{Add, AddAssign, Div, Mul, Sub, SubAssign};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Point<T> {
     pub width: T,
     pub height: T,
@@ -36,6 +36,25 @@ impl From<u128> for Point<i64> {
 //     }
 // }
 
+impl From<Point<i64>> for Point<f64> {
+    fn from(value: Point<i64>) -> Self {
+        Point {
+            width: value.width as f64,
+            height: value.height as f64,
+        }
+    }
+}
+
+impl Point<f64> {
+    /// Rounds to the nearest cell
+    pub fn round(self) -> Point<i64> {
+        Point {
+            width: self.width.round() as i64,
+            height: self.height.round() as i64,
+        }
+    }
+}
+
 impl<T> Default for Point<T>
 where
     T: AddAssign + SubAssign + Default,
@@ -1,19 +1,33 @@
-use std::{io::stdout, panic, time::Duration};
+use std::{collections::HashMap, io::stdout};
 
-use crossterm::event::{poll, read, Event, KeyCode};
+use crossterm::{
+    event::{Event, KeyCode},
+    style::Color,
+};
+use uuid::Uuid;
 
 use crate::{
     api::display::{get_screen_size, DisplayController, Output, Point},
-    components::{Drawable, DrawableState, Health},
-    entities::{Borders, Controller, Player},
-    helpers::{get_keyboard_event, get_now},
-    systems::{get_collision_summary, run_collision_detection, AsteroidController},
+    components::{Drawable, DrawableState, Health, SteeringTarget},
+    entities::{get_laser_beam_length, Borders, Controller, Player},
+    helpers::{get_keyboard_events, get_mouse_click, get_mouse_position, get_now, get_pressed_key},
+    systems::{
+        get_collision_events, get_collision_summary, get_key_name, get_safe_spawn_location,
+        is_bindable, run_collision_detection, Action, AsteroidController, BossController,
+        CollisionEvent, CollisionResponseTable, EnemyShipController, InputState, KeyMap,
+        PickupController, ScreenEffects, Sparks, Starfield, WaveDefinition, WaveDirector,
+        WaveSpawn,
+    },
     user_display::GAME_OVER_TEXT,
 };
 
 use super::{
     app_errors::AppError,
-    game_state::{GameState, ASTEROID_DESTROYED_POINTS},
+    game_state::{
+        GameScreen, GameState, ASTEROID_BULLSEYE_BONUS_POINTS, BOSS_DEFEATED_POINTS,
+        ENEMY_SHIP_DESTROYED_POINTS, SEEKER_MINE_DESTROYED_POINTS,
+    },
+    menu::{get_menu_input, Menu, MenuInput},
 };
 
 pub struct App {
@@ -23,83 +37,447 @@ pub struct App {
     borders: Borders,
     player: Player,
     asteroid_controller: AsteroidController,
-    dimensions: Point<i64>,
+    enemy_ship_controller: EnemyShipController,
+    boss_controller: BossController,
+    pickup_controller: PickupController,
+    wave_director: WaveDirector,
+    starfield: Starfield,
+    screen_effects: ScreenEffects,
+    sparks: Sparks,
+    collision_responses: CollisionResponseTable,
+    input_state: InputState,
+    title_menu: Menu,
+    pause_menu: Menu,
+    controls_menu: Menu,
+    /// The screen the controls menu returns to, this is either the title or pause screen
+    controls_parent_screen: GameScreen,
+    world_dimensions: Point<i64>,
 }
 
 const HUD_HEIGHT: i64 = 10;
 
+/// How many screens wide the world is, anything above 1 gives a horizontally scrolling level where the camera follows the player
+const WORLD_SCREEN_WIDTHS: i64 = 1;
+
+// Feedback shown when the player takes damage
+const DAMAGE_SHAKE_MAGNITUDE: i64 = 2;
+const DAMAGE_SHAKE_DURATION: u128 = 400;
+const DAMAGE_FLASH_COLOR: Color = Color::DarkRed;
+const DAMAGE_FLASH_DURATION: u128 = 150;
+// The player blinks for as long as they are invulnerable
+const INVULNERABILITY_BLINK_INTERVAL: u128 = 100;
+
+/// How much of a bullet's velocity, relative to an asteroid, is added to the asteroid when the asteroid survives the hit
+const ASTEROID_KNOCKBACK_FACTOR: f64 = 0.2;
+/// Ammunition hitting within this many cells of an asteroid's center is a bullseye
+const ASTEROID_BULLSEYE_RADIUS: f64 = 1.0;
+
+/// When true, asteroids bounce off each other and are knocked back by ammunition. This can be switched from the title menu, false plays like the classic game
+const ASTEROID_BOUNCE: bool = false;
+
+/// When true, asteroids are given randomly generated shapes, false uses the fixed sprites
+const GENERATED_ASTEROID_SHAPES: bool = true;
+
+/// Chance from 0 to 1 of a destroyed enemy dropping a pickup, bosses always drop one
+const PICKUP_DROP_CHANCE: f64 = 0.1;
+
+// The screen shakes when a boss moves to its next phase
+const BOSS_PHASE_SHAKE_MAGNITUDE: i64 = 2;
+const BOSS_PHASE_SHAKE_DURATION: u128 = 400;
+
+/// When true, losing a life removes the ship and respawns it at a safe location clear of asteroids
+const RESPAWN_ON_HIT: bool = true;
+
+/// Key bindings are loaded from and saved to this file, see KeyMap::from_config for the format
+const CONTROLS_CONFIG_PATH: &str = "controls.cfg";
+
+/// When true, clicking fires towards the mouse and the menus can be used with the mouse
+const MOUSE_ENABLED: bool = true;
+
+// Indexes of the title menu items
+const TITLE_MENU_PLAY: usize = 0;
+const TITLE_MENU_ASTEROID_MODE: usize = 1;
+const TITLE_MENU_CONTROLS: usize = 2;
+const TITLE_MENU_QUIT: usize = 3;
+
+// Indexes of the pause menu items
+const PAUSE_MENU_RESUME: usize = 0;
+const PAUSE_MENU_CONTROLS: usize = 1;
+const PAUSE_MENU_QUIT: usize = 2;
+
 pub type AppResult<T> = Result<T, AppError>;
 
 impl App {
-    pub fn new() -> AppResult<App> {
+    /// Creates the game, ready to be run
+    ///
+    /// # Arguments
+    ///
+    /// * `waves` - The waves to play, such as DEFAULT_WAVES or the waves of a level file. Must not be empty
+    pub fn new(waves: Vec<WaveDefinition>) -> AppResult<App> {
         let mut output = Output::new(stdout());
 
         let screen_size = get_screen_size();
 
         let game_screen_size = screen_size.sub_height(HUD_HEIGHT);
 
+        let world_dimensions = Point::new(
+            game_screen_size.width * WORLD_SCREEN_WIDTHS,
+            game_screen_size.height,
+        );
+
         let game_display_controller =
-            DisplayController::new(screen_size, Point::new(0, HUD_HEIGHT));
+            DisplayController::new(screen_size, Point::new(0, HUD_HEIGHT), world_dimensions);
 
         if let Some(error) = game_display_controller.as_ref().err() {
             output.close()?;
 
-            return Err(AppError::DisplayControllerError(error.clone()));
+            return Err(AppError::DisplayControllerError(*error));
         }
 
         let game_display_controller = game_display_controller.unwrap();
 
+        let game_state = GameState::new();
+
+        let mut asteroid_controller = AsteroidController::new(world_dimensions, game_state.seed);
+
+        asteroid_controller
+            .set_generated_shapes(GENERATED_ASTEROID_SHAPES)
+            .set_bounce(ASTEROID_BOUNCE);
+
+        // Offset the seed so the ships don't spawn in step with the asteroids
+        let enemy_ship_controller =
+            EnemyShipController::new(world_dimensions, game_state.seed.wrapping_add(1));
+
+        let wave_director = WaveDirector::new(waves, game_state.seed.wrapping_add(2));
+
+        let pickup_controller =
+            PickupController::new(PICKUP_DROP_CHANCE, game_state.seed.wrapping_add(3));
+
+        let key_map = KeyMap::load(CONTROLS_CONFIG_PATH)?;
+
+        let mut player = Player::new(None);
+
+        player.respawn_on_hit = RESPAWN_ON_HIT;
+        player.set_bounds(world_dimensions);
+        player.set_position(
+            get_player_spawn_location(&player.drawable.layout.dimensions, &world_dimensions).into(),
+        );
+
         Ok(App {
             display_controller: game_display_controller,
-            game_state: GameState::new(),
-            borders: Borders::new(&game_screen_size)?,
+            starfield: Starfield::new(game_screen_size, game_state.seed),
+            screen_effects: ScreenEffects::new(),
+            sparks: Sparks::new(world_dimensions),
+            collision_responses: CollisionResponseTable::default(),
+            title_menu: Menu::new("Asteroids", get_title_menu_items(ASTEROID_BOUNCE)),
+            pause_menu: Menu::new(
+                "Paused",
+                vec![
+                    "Resume".to_string(),
+                    "Controls".to_string(),
+                    "Quit".to_string(),
+                ],
+            ),
+            controls_menu: Menu::new("Controls", get_controls_menu_items(&key_map)),
+            controls_parent_screen: GameScreen::Title,
+            input_state: InputState::new(key_map),
+            game_state,
+            borders: Borders::new(&world_dimensions)?,
             output,
-            player: Player::new(None),
-            asteroid_controller: AsteroidController::new(100, game_screen_size),
-            dimensions: screen_size,
+            player,
+            asteroid_controller,
+            enemy_ship_controller,
+            boss_controller: BossController::new(world_dimensions),
+            pickup_controller,
+            wave_director,
+            world_dimensions,
         })
     }
 
     /// Reset method to be called at the start of each loop
     fn reset(&mut self) {
-        self.game_state.keyboard_event = None;
+        self.game_state.keyboard_events.clear();
 
         self.display_controller.layout.reset();
     }
 
-    /// Process the keyboard events, also returns true if the user closes the game with escape
+    /// Process the keyboard events, these are handled by the game or the open menu depending on the current screen
     fn handle_keyboard(&mut self) -> AppResult<()> {
-        let event = get_keyboard_event()?;
+        for event in get_keyboard_events()? {
+            match self.game_state.screen {
+                GameScreen::Title => self.handle_title_menu_event(&event),
+                GameScreen::Playing => self.handle_playing_event(&event),
+                GameScreen::Paused => self.handle_pause_menu_event(&event),
+                GameScreen::Controls => self.handle_controls_menu_event(&event),
+                GameScreen::Rebinding(action) => self.handle_rebinding_event(&event, action),
+            };
+
+            self.game_state.keyboard_events.push(event);
+
+            if !self.game_state.is_running() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_playing_event(&mut self, event: &Event) -> &mut Self {
+        self.input_state.handle_event(event);
+
+        match self.input_state.get_pressed_action(event) {
+            Some(Action::Quit) => {
+                self.game_state.stop_game();
+            }
+            Some(Action::Pause) => {
+                // Keys held when pausing would otherwise still be held when resuming
+                self.input_state.release_all();
+
+                self.pause_menu.selected = PAUSE_MENU_RESUME;
+                self.game_state.screen = GameScreen::Paused;
+            }
+            // The ship is out of play while it is waiting to respawn
+            Some(action) if !self.player.is_respawning() => {
+                self.player.handle_action(action);
+            }
+            _ => {}
+        }
+
+        // Clicking fires towards the mouse, clicks on the HUD are ignored
+        if let Some(target) = get_mouse_click(event)
+            .and_then(|position| self.display_controller.get_world_location(&position))
+        {
+            if !self.player.is_respawning() {
+                self.player.fire_at(target);
+            }
+        }
+
+        self
+    }
 
-        if let Some(event) = event {
-            if event == Event::Key(KeyCode::Esc.into()) {
+    fn handle_title_menu_event(&mut self, event: &Event) -> &mut Self {
+        match get_menu_event_input(
+            event,
+            &mut self.title_menu,
+            &self.display_controller,
+            &self.input_state.key_map,
+        ) {
+            Some(MenuInput::Previous) => {
+                self.title_menu.select_previous();
+            }
+            Some(MenuInput::Next) => {
+                self.title_menu.select_next();
+            }
+            Some(MenuInput::Back) => {
                 self.game_state.stop_game();
+            }
+            Some(MenuInput::Select) => match self.title_menu.selected {
+                TITLE_MENU_PLAY => {
+                    self.game_state.screen = GameScreen::Playing;
+                }
+                TITLE_MENU_ASTEROID_MODE => {
+                    let bounce = !self.asteroid_controller.get_bounce();
 
-                return Ok(());
+                    self.asteroid_controller.set_bounce(bounce);
+                    self.title_menu.set_items(get_title_menu_items(bounce));
+                }
+                TITLE_MENU_CONTROLS => {
+                    self.open_controls_menu();
+                }
+                TITLE_MENU_QUIT => {
+                    self.game_state.stop_game();
+                }
+                _ => {}
+            },
+            None => {}
+        }
+
+        self
+    }
+
+    fn handle_pause_menu_event(&mut self, event: &Event) -> &mut Self {
+        match get_menu_event_input(
+            event,
+            &mut self.pause_menu,
+            &self.display_controller,
+            &self.input_state.key_map,
+        ) {
+            Some(MenuInput::Previous) => {
+                self.pause_menu.select_previous();
+            }
+            Some(MenuInput::Next) => {
+                self.pause_menu.select_next();
+            }
+            Some(MenuInput::Back) => {
+                self.game_state.screen = GameScreen::Playing;
             }
+            Some(MenuInput::Select) => match self.pause_menu.selected {
+                PAUSE_MENU_RESUME => {
+                    self.game_state.screen = GameScreen::Playing;
+                }
+                PAUSE_MENU_CONTROLS => {
+                    self.open_controls_menu();
+                }
+                PAUSE_MENU_QUIT => {
+                    self.game_state.stop_game();
+                }
+                _ => {}
+            },
+            None => {}
+        }
+
+        self
+    }
+
+    fn open_controls_menu(&mut self) -> &mut Self {
+        self.controls_parent_screen = self.game_state.screen;
+        self.controls_menu.selected = 0;
+        self.game_state.screen = GameScreen::Controls;
+
+        self
+    }
+
+    fn handle_controls_menu_event(&mut self, event: &Event) -> &mut Self {
+        let back_index = Action::ALL.len();
 
-            self.player.handle_event(&event);
+        match get_menu_event_input(
+            event,
+            &mut self.controls_menu,
+            &self.display_controller,
+            &self.input_state.key_map,
+        ) {
+            Some(MenuInput::Previous) => {
+                self.controls_menu.select_previous();
+            }
+            Some(MenuInput::Next) => {
+                self.controls_menu.select_next();
+            }
+            Some(MenuInput::Select) if self.controls_menu.selected < back_index => {
+                let action = Action::ALL[self.controls_menu.selected];
 
-            self.game_state.keyboard_event = Some(event);
+                self.controls_menu.title = format!("Press a key for {}", action.get_label());
+                self.game_state.screen = GameScreen::Rebinding(action);
+            }
+            Some(MenuInput::Select) | Some(MenuInput::Back) => {
+                self.game_state.screen = self.controls_parent_screen;
+            }
+            None => {}
         }
 
-        Ok(())
+        self
+    }
+
+    /// Binds the next key pressed to the action and saves the controls, escape cancels the rebinding. Keys that can't be saved to the controls config are refused
+    fn handle_rebinding_event(&mut self, event: &Event, action: Action) -> &mut Self {
+        let key = match get_pressed_key(event) {
+            Some(key) => key,
+            None => return self,
+        };
+
+        if key != KeyCode::Esc && !is_bindable(key) {
+            self.controls_menu.title = format!(
+                "{} can't be bound, press another key for {}",
+                get_key_name(key),
+                action.get_label()
+            );
+
+            return self;
+        }
+
+        self.controls_menu.title = "Controls".to_string();
+
+        if key != KeyCode::Esc {
+            self.input_state.key_map.rebind(action, key);
+
+            // Failing to save only loses the bindings for future games, so it's shown in the menu rather than stopping this one
+            if let Err(error) = self.input_state.key_map.save(CONTROLS_CONFIG_PATH) {
+                self.controls_menu.title = format!("Couldn't save the controls: {}", error);
+            }
+        }
+
+        self.controls_menu
+            .set_items(get_controls_menu_items(&self.input_state.key_map));
+
+        self.game_state.screen = GameScreen::Controls;
+
+        self
     }
 
     fn run_game_loop(&mut self) -> AppResult<()> {
+        let mut previous_game_loop_start = get_now();
+
         while self.game_state.is_running() {
+            // Everything moves by the time the whole of the previous loop took, including updating and drawing, so motion doesn't depend on the frame rate
             let game_loop_start = get_now();
+            let game_loop_duration = game_loop_start.saturating_sub(previous_game_loop_start);
+
+            previous_game_loop_start = game_loop_start;
+
             self.reset();
 
             self.handle_keyboard()?;
 
-            let game_loop_duration = get_now() - game_loop_start;
+            // The game is frozen while a menu is open
+            if self.game_state.screen != GameScreen::Playing {
+                self.draw_all_entities()?;
 
-            self.asteroid_controller
-                .handle_game_loop(game_loop_duration);
+                continue;
+            }
+
+            self.input_state.handle_game_loop(game_loop_duration);
+
+            if !self.player.is_respawning() {
+                self.player.handle_input_state(&self.input_state);
+            }
+
+            self.handle_waves(game_loop_duration);
+
+            // Enemies hold their fire while there is no ship to shoot at, and mines back off while the player can't fight back
+            let enemy_target = (!self.player.is_respawning())
+                .then(|| SteeringTarget::from(self.player.get_drawable_state()));
+
+            self.enemy_ship_controller
+                .set_mines_fleeing(self.player.is_invulnerable())
+                .handle_game_loop(game_loop_duration, enemy_target);
+
+            self.boss_controller.handle_game_loop(
+                game_loop_duration,
+                enemy_target.map(|target| target.location.round()),
+            );
+
+            let missile_targets: Vec<SteeringTarget> = self
+                .get_enemy_drawable_states()
+                .into_iter()
+                .map(SteeringTarget::from)
+                .collect();
+
+            self.player
+                .steer_missiles(&missile_targets, game_loop_duration);
+
+            self.starfield.handle_game_loop(game_loop_duration);
+
+            self.screen_effects.handle_game_loop(game_loop_duration);
+
+            self.sparks.handle_game_loop(game_loop_duration);
+
+            if self.player.handle_game_loop(game_loop_duration) {
+                self.respawn_player();
+            }
 
             self.update_positions(game_loop_duration);
 
+            // The beam is measured after everything has moved so it reaches exactly what it is drawn touching
+            let laser_beam_length = get_laser_beam_length(
+                self.player.get_laser_beam_origin(),
+                &self.get_enemy_drawable_states(),
+            );
+
+            self.player.handle_laser_beam(
+                self.input_state.is_action_held(Action::Fire),
+                laser_beam_length,
+                game_loop_duration,
+            );
+
             self.handle_collisions()?;
 
             self.draw_all_entities()?;
@@ -110,30 +488,20 @@ impl App {
 
     pub fn run(&mut self) -> AppResult<()> {
         self.start()?;
-        dbg!("YO");
-        let result = self.run_game_loop();
 
-        dbg!("UHH");
-
-        if let Err(err) = result.as_ref() {
-            match err {
-                AppError::OutOfLives => {
-                    self.reset();
-                    dbg!("HERE22");
-                    let result = self.display_controller.layout.draw_str(
-                        GAME_OVER_TEXT,
-                        &Point::new(0, 0),
-                        None,
-                        None,
-                    );
+        let result = self.run_game_loop();
 
-                    dbg!(result.err());
+        if let Err(AppError::OutOfLives) = result {
+            self.reset();
 
-                    // while true {}
-                }
-                _ => {
-                    dbg!("IN THIS ONE");
-                }
+            // The game over text is wider than some terminals, it is only shown when it fits
+            if self
+                .display_controller
+                .layout
+                .draw_str(GAME_OVER_TEXT, &Point::new(0, 0), None, None)
+                .is_ok()
+            {
+                self.output.print_display(&self.display_controller.layout)?;
             }
         }
 
@@ -143,38 +511,161 @@ impl App {
     }
 
     fn handle_collisions(&mut self) -> AppResult<&mut Self> {
-        let collision_results = get_collision_summary(run_collision_detection(
+        let collision_results = run_collision_detection(
             self.get_all_drawable_states(),
-            &self.dimensions,
-        ));
+            &self.world_dimensions,
+            &self.collision_responses,
+        );
+
+        // Bosses only take the damage from hits on their weak points
+        let mut boss_damage: HashMap<Uuid, u32> = HashMap::new();
+
+        // Events are handled first, the damage below removes destroyed entities
+        for collision_event in get_collision_events(&collision_results) {
+            for (uuid, damage) in [
+                (
+                    collision_event.affected_uuid,
+                    collision_event.affected_damage,
+                ),
+                (collision_event.other_uuid, collision_event.other_damage),
+            ] {
+                if let Some(boss) = self.boss_controller.entity_controller.get_entity(uuid) {
+                    *boss_damage.entry(uuid).or_default() +=
+                        boss.get_weak_point_damage(&collision_event.cells, damage);
+                }
+            }
+
+            self.handle_collision_event(&collision_event);
+        }
+
+        let collision_results = get_collision_summary(collision_results);
 
         for (uuid, collision) in collision_results {
-            // Asteroid collision
-            if self
+            // Destroyed enemies drop pickups where they were, so each enemy's center is found before the damage removes it
+            if let Some(asteroid) = self
                 .asteroid_controller
                 .entity_controller
-                .has_entity(collision.uuid)
+                .get_entity(collision.uuid)
             {
-                let destroyed = self
+                // Asteroid collision
+                let enemy_center = asteroid.get_drawable_state().get_center();
+
+                if let Some(size) = self
                     .asteroid_controller
+                    .apply_asteroid_damage(uuid, collision.damage)
+                {
+                    self.game_state.score += size.get_points();
+                    self.drop_pickup(enemy_center, false);
+                }
+            } else if let Some(enemy_ship) = self
+                .enemy_ship_controller
+                .entity_controller
+                .get_entity(uuid)
+            {
+                // Enemy ship collision
+                let enemy_center = enemy_ship.get_drawable_state().get_center();
+                let destroyed = self
+                    .enemy_ship_controller
                     .entity_controller
                     .apply_entity_damage(uuid, collision.damage);
 
                 if destroyed {
-                    self.game_state.score += ASTEROID_DESTROYED_POINTS;
+                    self.game_state.score += ENEMY_SHIP_DESTROYED_POINTS;
+                    self.drop_pickup(enemy_center, false);
+                }
+            } else if let Some(boss) = self.boss_controller.entity_controller.get_entity(uuid) {
+                // Boss collision
+                let enemy_center = boss.get_drawable_state().get_center();
+                let phase_index = boss.get_phase_index();
+
+                let destroyed = self
+                    .boss_controller
+                    .entity_controller
+                    .apply_entity_damage(uuid, boss_damage.get(&uuid).copied().unwrap_or(0));
+
+                if destroyed {
+                    self.game_state.score += BOSS_DEFEATED_POINTS;
+                    self.drop_pickup(enemy_center, true);
+                } else if self
+                    .boss_controller
+                    .entity_controller
+                    .get_entity(uuid)
+                    .is_some_and(|boss| boss.get_phase_index() != phase_index)
+                {
+                    self.screen_effects
+                        .trigger_shake(BOSS_PHASE_SHAKE_MAGNITUDE, BOSS_PHASE_SHAKE_DURATION);
                 }
+            } else if self
+                .boss_controller
+                .bullet_entity_controller
+                .has_entity(uuid)
+            {
+                // Boss bullet collision
+                self.boss_controller
+                    .bullet_entity_controller
+                    .apply_entity_damage(uuid, collision.damage);
+            } else if let Some(mine) = self
+                .enemy_ship_controller
+                .mine_entity_controller
+                .get_entity(uuid)
+            {
+                // Seeker mine collision
+                let enemy_center = mine.get_drawable_state().get_center();
+                let destroyed = self
+                    .enemy_ship_controller
+                    .mine_entity_controller
+                    .apply_entity_damage(uuid, collision.damage);
+
+                if destroyed {
+                    self.game_state.score += SEEKER_MINE_DESTROYED_POINTS;
+                    self.drop_pickup(enemy_center, false);
+                }
+            } else if self
+                .enemy_ship_controller
+                .bullet_entity_controller
+                .has_entity(uuid)
+            {
+                // Enemy bullet collision
+                self.enemy_ship_controller
+                    .bullet_entity_controller
+                    .apply_entity_damage(uuid, collision.damage);
+            } else if self.pickup_controller.entity_controller.has_entity(uuid) {
+                // Pickup collision, the effect is granted by handle_collision_event
+                self.pickup_controller
+                    .entity_controller
+                    .apply_entity_damage(uuid, collision.damage);
             } else if self.player.bullet_entity_controller.has_entity(uuid) {
                 // Bullet collision
                 self.player
                     .bullet_entity_controller
                     .apply_entity_damage(uuid, collision.damage);
+            } else if self.player.laser_beam.drawable.uuid == uuid {
+                // Laser beam collision, the beam isn't worn down by what it hits
             } else if self.player.drawable.uuid == uuid {
-                // Player collision
+                // Player collision, collecting pickups doesn't hurt and shields absorb everything else
+                if collision.damage == 0 || self.player.is_shielded() {
+                    continue;
+                }
+
                 self.player.apply_damage(collision.damage);
 
                 if self.player.get_health() == 0 {
                     return Err(AppError::OutOfLives);
                 }
+
+                self.player.handle_hit();
+
+                self.screen_effects
+                    .trigger_shake(DAMAGE_SHAKE_MAGNITUDE, DAMAGE_SHAKE_DURATION)
+                    .trigger_flash(DAMAGE_FLASH_COLOR, DAMAGE_FLASH_DURATION);
+
+                if self.player.is_invulnerable() {
+                    self.screen_effects.trigger_blink(
+                        uuid,
+                        self.player.invulnerability_duration,
+                        INVULNERABILITY_BLINK_INTERVAL,
+                    );
+                }
             } else {
                 // Use a lazy panic here because this shouldn't happen
                 panic!("UUID missing from all arrays");
@@ -184,9 +675,96 @@ impl App {
         Ok(self)
     }
 
+    /// Drops a pickup where an enemy was destroyed, at the drop chance unless it is guaranteed
+    fn drop_pickup(&mut self, enemy_center: Point<f64>, guaranteed: bool) -> &mut Self {
+        if guaranteed {
+            self.pickup_controller.drop_pickup(enemy_center);
+        } else {
+            self.pickup_controller.handle_enemy_destroyed(enemy_center);
+        }
+
+        self
+    }
+
+    /// Reacts to where and how drawables hit each other, sparks fly from ammunition hitting asteroids, the asteroid is knocked back and bullseyes earn bonus points. Asteroids hitting each other bounce apart and pickups the player touches grant their effect
+    fn handle_collision_event(&mut self, collision_event: &CollisionEvent) -> &mut Self {
+        for (player_uuid, pickup_uuid) in [
+            (collision_event.affected_uuid, collision_event.other_uuid),
+            (collision_event.other_uuid, collision_event.affected_uuid),
+        ] {
+            if player_uuid != self.player.drawable.uuid {
+                continue;
+            }
+
+            if let Some(pickup) = self
+                .pickup_controller
+                .entity_controller
+                .get_entity(pickup_uuid)
+            {
+                let kind = pickup.kind;
+
+                self.player.apply_pickup(kind);
+
+                return self;
+            }
+        }
+
+        let asteroid_entity_controller = &self.asteroid_controller.entity_controller;
+
+        if asteroid_entity_controller.has_entity(collision_event.affected_uuid)
+            && asteroid_entity_controller.has_entity(collision_event.other_uuid)
+        {
+            self.asteroid_controller
+                .handle_bounce(collision_event.affected_uuid, collision_event.other_uuid);
+
+            return self;
+        }
+
+        if !self
+            .player
+            .bullet_entity_controller
+            .has_entity(collision_event.affected_uuid)
+        {
+            return self;
+        }
+
+        let knockback = self.asteroid_controller.get_bounce();
+
+        let Some(asteroid) = self
+            .asteroid_controller
+            .entity_controller
+            .get_entity_mut(collision_event.other_uuid)
+        else {
+            return self;
+        };
+
+        // The bullet's velocity relative to the asteroid
+        let impact_velocity = Point::default() - collision_event.relative_velocity;
+
+        let offset_from_center = collision_event.contact_point - asteroid.drawable.get_center();
+
+        if offset_from_center.width.hypot(offset_from_center.height) <= ASTEROID_BULLSEYE_RADIUS {
+            self.game_state.score += ASTEROID_BULLSEYE_BONUS_POINTS;
+        }
+
+        // Destroyed asteroids are removed when the damage is applied, so this only affects asteroids that survive
+        if knockback && asteroid.get_health() > collision_event.other_damage {
+            asteroid.drawable.velocity = asteroid.drawable.velocity
+                + impact_velocity
+                    * Point::new(ASTEROID_KNOCKBACK_FACTOR, ASTEROID_KNOCKBACK_FACTOR);
+        }
+
+        // Sparks bounce back off the asteroid
+        self.sparks.spawn(
+            collision_event.contact_point,
+            collision_event.relative_velocity,
+        );
+
+        self
+    }
+
     fn update_positions(&mut self, game_loop_duration: u128) -> &mut Self {
-        self.player
-            .update_position(Some(&self.dimensions), game_loop_duration);
+        self.player.update_position(game_loop_duration);
 
         self.player
             .bullet_entity_controller
@@ -196,24 +774,164 @@ impl App {
             .entity_controller
             .update_entity_positions(game_loop_duration);
 
+        self.enemy_ship_controller
+            .entity_controller
+            .update_entity_positions(game_loop_duration);
+
+        self.enemy_ship_controller
+            .bullet_entity_controller
+            .update_entity_positions(game_loop_duration);
+
+        self.enemy_ship_controller
+            .mine_entity_controller
+            .update_entity_positions(game_loop_duration);
+
+        self.boss_controller
+            .entity_controller
+            .update_entity_positions(game_loop_duration);
+
+        self.boss_controller
+            .bullet_entity_controller
+            .update_entity_positions(game_loop_duration);
+
+        self.pickup_controller
+            .entity_controller
+            .update_entity_positions(game_loop_duration);
+
         self
     }
 
-    fn get_all_drawable_states(&self) -> Vec<&DrawableState> {
-        let mut drawable_items: Vec<&DrawableState> = vec![self.player.get_drawable_state()];
+    /// Spawns whatever the wave director asks for and keeps the game state's wave in step with it
+    fn handle_waves(&mut self, game_loop_duration: u128) -> &mut Self {
+        let enemies_remaining = self.get_enemy_drawable_states().len();
+
+        // Lanes are fractions of the world's width so levels work at any screen size
+        let world_width = self.world_dimensions.width;
+        let get_column = |lane: Option<f64>| {
+            lane.map(|lane| ((lane * world_width as f64) as i64).clamp(0, world_width - 1))
+        };
+
+        match self
+            .wave_director
+            .handle_game_loop(game_loop_duration, enemies_remaining)
+        {
+            Some(WaveSpawn::Asteroid { lane, velocity }) => {
+                self.asteroid_controller
+                    .spawn_asteroid(get_column(lane), velocity);
+            }
+            Some(WaveSpawn::EnemyShip { lane }) => {
+                self.enemy_ship_controller
+                    .spawn_enemy_ship(get_column(lane));
+            }
+            Some(WaveSpawn::SeekerMine { lane }) => {
+                self.enemy_ship_controller
+                    .spawn_seeker_mine(get_column(lane));
+            }
+            Some(WaveSpawn::Boss) => {
+                self.boss_controller.spawn_boss();
+            }
+            None => {}
+        }
+
+        self.game_state.wave = self.wave_director.get_wave_number();
+
+        self
+    }
+
+    /// Places the player back at the spawn location, or the nearest location that is clear of asteroids and enemy ships, and starts their invulnerability
+    fn respawn_player(&mut self) -> &mut Self {
+        let enemy_states = self.get_enemy_drawable_states();
+
+        let location = get_safe_spawn_location(
+            get_player_spawn_location(
+                &self.player.drawable.layout.dimensions,
+                &self.world_dimensions,
+            ),
+            &self.player.drawable.layout.dimensions,
+            &self.world_dimensions,
+            &enemy_states,
+        );
+
+        self.player.respawn(location);
+
+        self.screen_effects.trigger_blink(
+            self.player.drawable.uuid,
+            self.player.invulnerability_duration,
+            INVULNERABILITY_BLINK_INTERVAL,
+        );
+
+        self
+    }
+
+    /// Returns the drawable states of everything the player can shoot down
+    fn get_enemy_drawable_states(&self) -> Vec<&DrawableState> {
+        let mut drawable_items = self
+            .asteroid_controller
+            .entity_controller
+            .get_all_drawable_states();
 
         drawable_items.append(
             &mut self
-                .asteroid_controller
+                .enemy_ship_controller
+                .entity_controller
+                .get_all_drawable_states(),
+        );
+        drawable_items.append(
+            &mut self
+                .enemy_ship_controller
+                .mine_entity_controller
+                .get_all_drawable_states(),
+        );
+        drawable_items.append(
+            &mut self
+                .boss_controller
                 .entity_controller
                 .get_all_drawable_states(),
         );
+
+        drawable_items
+    }
+
+    /// Returns the drawable states that take part in collision detection
+    fn get_all_drawable_states(&self) -> Vec<&DrawableState> {
+        let mut drawable_items: Vec<&DrawableState> = vec![];
+
+        // The ship is out of play while waiting to respawn, while invulnerable its collision mask only lets it collect pickups
+        if !self.player.is_respawning() {
+            drawable_items.push(self.player.get_drawable_state());
+        }
+
+        drawable_items.append(&mut self.get_enemy_drawable_states());
+
+        // The beam deals its damage on ticks rather than every loop it touches something
+        if self.player.laser_beam.is_damage_tick() {
+            drawable_items.push(self.player.laser_beam.get_drawable_state());
+        }
+
         drawable_items.append(
             &mut self
                 .player
                 .bullet_entity_controller
                 .get_all_drawable_states(),
         );
+        drawable_items.append(
+            &mut self
+                .enemy_ship_controller
+                .bullet_entity_controller
+                .get_all_drawable_states(),
+        );
+        drawable_items.append(
+            &mut self
+                .boss_controller
+                .bullet_entity_controller
+                .get_all_drawable_states(),
+        );
+        drawable_items.append(
+            &mut self
+                .pickup_controller
+                .entity_controller
+                .get_all_drawable_states(),
+        );
 
         drawable_items
     }
@@ -221,10 +939,26 @@ impl App {
     /// Method to handle drawing all the entities that will be rendered
     fn draw_all_entities(&mut self) -> AppResult<&mut Self> {
         self.display_controller
-            .draw_drawable(&self.borders.get_drawable_state())?;
+            .apply_screen_effects(&self.screen_effects)
+            .camera
+            .follow(self.player.get_drawable_state());
 
+        // The starfield is drawn first so it sits beneath everything else, it is fixed to the screen rather than the world
         self.display_controller
-            .draw_drawable(&self.player.get_drawable_state())?;
+            .draw_screen_drawable(self.starfield.get_drawable_state())?;
+
+        self.display_controller
+            .draw_drawable(self.borders.get_drawable_state())?;
+
+        if !self.player.is_respawning() {
+            self.display_controller
+                .draw_drawable(self.player.get_drawable_state())?;
+        }
+
+        if self.player.laser_beam.is_firing() {
+            self.display_controller
+                .draw_drawable(self.player.laser_beam.get_drawable_state())?;
+        }
 
         // Draw all the entities in the bullet and asteroid controller
         self.display_controller
@@ -233,9 +967,64 @@ impl App {
         self.display_controller
             .draw_entity_controller_items(&mut self.asteroid_controller.entity_controller);
 
+        self.display_controller
+            .draw_entity_controller_items(&mut self.enemy_ship_controller.entity_controller);
+
+        self.display_controller
+            .draw_entity_controller_items(&mut self.enemy_ship_controller.mine_entity_controller);
+
+        self.display_controller
+            .draw_entity_controller_items(&mut self.enemy_ship_controller.bullet_entity_controller);
+
+        self.display_controller
+            .draw_entity_controller_items(&mut self.boss_controller.entity_controller);
+
+        self.display_controller
+            .draw_entity_controller_items(&mut self.boss_controller.bullet_entity_controller);
+
+        self.display_controller
+            .draw_entity_controller_items(&mut self.pickup_controller.entity_controller);
+
+        self.display_controller
+            .draw_drawable(self.sparks.get_drawable_state())?;
+
         self.display_controller
             .draw_game_state(&self.game_state, self.player.get_health())?;
 
+        self.display_controller
+            .draw_power_ups(&self.player.get_active_power_ups())?
+            .draw_weapons(&self.player.weapons, self.player.selected_weapon)?;
+
+        if let Some(boss) = self.boss_controller.get_active_boss() {
+            self.display_controller
+                .draw_boss_health(boss.get_health(), boss.max_health)?;
+        }
+
+        if self.game_state.screen == GameScreen::Playing {
+            if let Some(banner) = self.wave_director.get_banner() {
+                self.display_controller.draw_banner(&banner)?;
+            }
+        }
+
+        match self.game_state.screen {
+            GameScreen::Title => {
+                self.display_controller.draw_menu(&self.title_menu)?;
+            }
+            GameScreen::Playing => {
+                if let Some(mouse_position) = self.input_state.get_mouse_position() {
+                    self.display_controller.draw_crosshair(&mouse_position)?;
+                }
+            }
+            GameScreen::Paused => {
+                self.display_controller.draw_menu(&self.pause_menu)?;
+            }
+            GameScreen::Controls | GameScreen::Rebinding(_) => {
+                self.display_controller.draw_menu(&self.controls_menu)?;
+            }
+        }
+
+        self.display_controller.draw_flash()?;
+
         self.output.print_display(&self.display_controller.layout)?;
 
         Ok(self)
@@ -243,7 +1032,7 @@ impl App {
 
     pub fn start(&mut self) -> AppResult<()> {
         self.game_state.start_game();
-        self.output.start()?;
+        self.output.start(MOUSE_ENABLED)?;
 
         Ok(())
     }
@@ -254,3 +1043,60 @@ impl App {
         Ok(())
     }
 }
+
+/// The player spawns in the center of the bottom of the world
+fn get_player_spawn_location(
+    player_dimensions: &Point<i64>,
+    world_dimensions: &Point<i64>,
+) -> Point<i64> {
+    Point::new(
+        (world_dimensions.width - player_dimensions.width) / 2,
+        // Keep the player off the border
+        world_dimensions.height - player_dimensions.height - 1,
+    )
+}
+
+/// The title menu items, the asteroid mode item shows whether asteroids bounce
+fn get_title_menu_items(asteroid_bounce: bool) -> Vec<String> {
+    let asteroid_mode = if asteroid_bounce { "Bounce" } else { "Classic" };
+
+    vec![
+        "Play".to_string(),
+        format!("Asteroids: {}", asteroid_mode),
+        "Controls".to_string(),
+        "Quit".to_string(),
+    ]
+}
+
+/// Lists each action with its bound keys, followed by an item to go back
+fn get_controls_menu_items(key_map: &KeyMap) -> Vec<String> {
+    Action::ALL
+        .iter()
+        .map(|action| {
+            format!(
+                "{}: {}",
+                action.get_label(),
+                key_map.get_keys_label(*action)
+            )
+        })
+        .chain(["Back".to_string()])
+        .collect()
+}
+
+/// Converts an event into menu input. Moving the mouse over an item selects it and clicking an item chooses it, otherwise the keyboard is used
+fn get_menu_event_input(
+    event: &Event,
+    menu: &mut Menu,
+    display_controller: &DisplayController,
+    key_map: &KeyMap,
+) -> Option<MenuInput> {
+    if let Some(position) = get_mouse_position(event) {
+        let index = display_controller.get_menu_item_at(menu, &position)?;
+
+        menu.selected = index;
+
+        return get_mouse_click(event).map(|_| MenuInput::Select);
+    }
+
+    get_menu_input(event, key_map)
+}
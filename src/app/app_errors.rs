@@ -1,11 +1,28 @@
+use std::fmt;
+
 use crossterm::ErrorKind;
 
-use crate::api::display::DisplayControllerError;
+use crate::{api::display::DisplayControllerError, systems::KeyMapError};
 
 #[derive(Debug)]
 pub enum AppError {
     OutOfLives,
     DisplayControllerError(DisplayControllerError),
+    ControlsConfigError(KeyMapError),
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::OutOfLives => write!(formatter, "out of lives"),
+            Self::DisplayControllerError(error) => {
+                write!(formatter, "couldn't draw the game: {:?}", error)
+            }
+            Self::ControlsConfigError(error) => {
+                write!(formatter, "couldn't load the controls: {}", error)
+            }
+        }
+    }
 }
 
 impl From<DisplayControllerError> for AppError {
@@ -14,6 +31,12 @@ impl From<DisplayControllerError> for AppError {
     }
 }
 
+impl From<KeyMapError> for AppError {
+    fn from(error: KeyMapError) -> Self {
+        Self::ControlsConfigError(error)
+    }
+}
+
 impl From<ErrorKind> for AppError {
     fn from(_: ErrorKind) -> Self {
         Self::DisplayControllerError(DisplayControllerError::CrossTermWriteError)
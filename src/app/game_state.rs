@@ -1,20 +1,48 @@
 use crossterm::event::Event;
 
+use crate::systems::Action;
+
+/// The screen currently shown, the game is only updated while playing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameScreen {
+    /// Shown before the game starts
+    Title,
+    Playing,
+    Paused,
+    /// Lists the key bindings so they can be changed
+    Controls,
+    /// Waiting for the key to bind to the action
+    Rebinding(Action),
+}
+
 pub struct GameState {
     running: bool,
-    pub keyboard_event: Option<Event>,
+    /// All the events received during the current loop
+    pub keyboard_events: Vec<Event>,
     pub score: u64,
+    /// Seed for everything that should be reproducible within a game, such as the starfield
+    pub seed: u64,
+    pub screen: GameScreen,
+    /// The current wave, starting from 1
+    pub wave: u32,
     // pub drawables_in_frame: Vec<dyn Drawable>,
 }
 
-pub const ASTEROID_DESTROYED_POINTS: u64 = 1;
+pub const ENEMY_SHIP_DESTROYED_POINTS: u64 = 5;
+pub const SEEKER_MINE_DESTROYED_POINTS: u64 = 2;
+pub const BOSS_DEFEATED_POINTS: u64 = 50;
+/// Extra points for ammunition hitting close to the center of an asteroid
+pub const ASTEROID_BULLSEYE_BONUS_POINTS: u64 = 2;
 
 impl GameState {
     pub fn new() -> Self {
         Self {
             running: false,
-            keyboard_event: None,
+            keyboard_events: vec![],
             score: 0,
+            seed: rand::random(),
+            screen: GameScreen::Title,
+            wave: 1,
         }
     }
 
@@ -0,0 +1,90 @@
+use crossterm::event::{Event, KeyCode};
+
+use crate::{
+    helpers::get_pressed_key,
+    systems::{Action, KeyMap},
+};
+
+/// Input that a menu responds to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MenuInput {
+    Previous,
+    Next,
+    Select,
+    Back,
+}
+
+/// Converts an event into menu input. The arrow keys, enter and escape always work so the menus can't become unusable through rebinding, the bound movement, fire and pause keys work as well
+pub fn get_menu_input(event: &Event, key_map: &KeyMap) -> Option<MenuInput> {
+    let key = get_pressed_key(event)?;
+
+    match (key, key_map.get_action(key)) {
+        (KeyCode::Up, _) | (_, Some(Action::MoveUp)) => Some(MenuInput::Previous),
+        (KeyCode::Down, _) | (_, Some(Action::MoveDown)) => Some(MenuInput::Next),
+        (KeyCode::Enter, _) | (_, Some(Action::Fire)) => Some(MenuInput::Select),
+        (KeyCode::Esc, _) | (_, Some(Action::Pause)) => Some(MenuInput::Back),
+        _ => None,
+    }
+}
+
+/// A vertical list of options with one selected at a time, rendered by the display controller on top of the game
+#[derive(Debug)]
+pub struct Menu {
+    pub title: String,
+    pub items: Vec<String>,
+    pub selected: usize,
+}
+
+impl Menu {
+    pub fn new(title: &str, items: Vec<String>) -> Self {
+        Self {
+            title: title.to_string(),
+            items,
+            selected: 0,
+        }
+    }
+
+    /// Moves the selection down, wrapping around to the first item
+    pub fn select_next(&mut self) -> &mut Self {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + 1) % self.items.len();
+        }
+
+        self
+    }
+
+    /// Moves the selection up, wrapping around to the last item
+    pub fn select_previous(&mut self) -> &mut Self {
+        if !self.items.is_empty() {
+            self.selected = (self.selected + self.items.len() - 1) % self.items.len();
+        }
+
+        self
+    }
+
+    /// Replaces the items while keeping the selected index where possible
+    pub fn set_items(&mut self, items: Vec<String>) -> &mut Self {
+        self.selected = self.selected.min(items.len().saturating_sub(1));
+        self.items = items;
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Menu;
+
+    #[test]
+    fn it_wraps_the_selection() {
+        let mut menu = Menu::new("Menu", vec!["First".to_string(), "Second".to_string()]);
+
+        menu.select_previous();
+
+        assert_eq!(menu.selected, 1);
+
+        menu.select_next();
+
+        assert_eq!(menu.selected, 0);
+    }
+}
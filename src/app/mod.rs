@@ -1,6 +1,10 @@
+#[allow(clippy::module_inception)]
 mod app;
 mod app_errors;
 mod game_state;
+mod menu;
 
 pub use app::App;
+pub use app_errors::AppError;
 pub use game_state::*;
+pub use menu::*;
@@ -0,0 +1,162 @@
+use super::DrawableType;
+
+/// The layer a drawable collides on, drawables only collide when each one's mask contains the other's layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CollisionLayer {
+    Player,
+    PlayerAmmunition,
+    Enemy,
+    EnemyAmmunition,
+    Pickup,
+    Border,
+    /// Never collides with anything
+    Decoration,
+}
+
+impl CollisionLayer {
+    fn get_bit(&self) -> u32 {
+        1 << (*self as u32)
+    }
+
+    /// The layer and mask drawables of the type start with
+    pub fn get_defaults(drawable_type: DrawableType) -> (CollisionLayer, CollisionMask) {
+        match drawable_type {
+            DrawableType::Player => (
+                CollisionLayer::Player,
+                CollisionMask::from_layers(&[
+                    CollisionLayer::Enemy,
+                    CollisionLayer::EnemyAmmunition,
+                    CollisionLayer::Pickup,
+                ]),
+            ),
+            DrawableType::Ammunition(..) => (
+                CollisionLayer::PlayerAmmunition,
+                CollisionMask::from_layers(&[CollisionLayer::Enemy]),
+            ),
+            DrawableType::Enemy(..) => (
+                CollisionLayer::Enemy,
+                CollisionMask::from_layers(&[
+                    CollisionLayer::Player,
+                    CollisionLayer::PlayerAmmunition,
+                ]),
+            ),
+            DrawableType::EnemyAmmunition(..) => (
+                CollisionLayer::EnemyAmmunition,
+                CollisionMask::from_layers(&[CollisionLayer::Player]),
+            ),
+            DrawableType::Pickup => (
+                CollisionLayer::Pickup,
+                CollisionMask::from_layers(&[CollisionLayer::Player]),
+            ),
+            DrawableType::Border => (CollisionLayer::Border, CollisionMask::NONE),
+            DrawableType::Background => (CollisionLayer::Decoration, CollisionMask::NONE),
+        }
+    }
+}
+
+/// The set of layers a drawable interacts with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CollisionMask(u32);
+
+impl CollisionMask {
+    pub const NONE: CollisionMask = CollisionMask(0);
+
+    pub fn from_layers(layers: &[CollisionLayer]) -> Self {
+        layers
+            .iter()
+            .fold(Self::NONE, |mask, layer| mask.with_layer(*layer))
+    }
+
+    pub fn with_layer(self, layer: CollisionLayer) -> Self {
+        Self(self.0 | layer.get_bit())
+    }
+
+    pub fn contains(&self, layer: CollisionLayer) -> bool {
+        self.0 & layer.get_bit() != 0
+    }
+}
+
+/// Whether drawables on the layers with the masks collide, both masks need to contain the other drawable's layer
+pub fn get_can_layers_collide(
+    (first_layer, first_mask): (CollisionLayer, CollisionMask),
+    (second_layer, second_mask): (CollisionLayer, CollisionMask),
+) -> bool {
+    first_mask.contains(second_layer) && second_mask.contains(first_layer)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::DrawableType;
+
+    use super::{get_can_layers_collide, CollisionLayer, CollisionMask};
+
+    #[test]
+    fn it_only_collides_when_both_masks_contain_the_other_layer() {
+        let player = CollisionLayer::get_defaults(DrawableType::Player);
+        let ammunition = CollisionLayer::get_defaults(DrawableType::Ammunition(1));
+        let enemy = CollisionLayer::get_defaults(DrawableType::Enemy(1));
+
+        assert!(get_can_layers_collide(player, enemy));
+        assert!(get_can_layers_collide(ammunition, enemy));
+        assert!(!get_can_layers_collide(player, ammunition));
+        assert!(!get_can_layers_collide(enemy, enemy));
+
+        // Friendly fire only needs the player to accept the ammunition layer and the other way around
+        let friendly_fire_player = (
+            player.0,
+            player.1.with_layer(CollisionLayer::PlayerAmmunition),
+        );
+        let friendly_fire_ammunition = (
+            ammunition.0,
+            ammunition.1.with_layer(CollisionLayer::Player),
+        );
+
+        assert!(get_can_layers_collide(
+            friendly_fire_player,
+            friendly_fire_ammunition
+        ));
+        assert!(!CollisionMask::NONE.contains(CollisionLayer::Player));
+    }
+
+    #[test]
+    fn it_only_collides_pickups_with_the_player() {
+        let pickup = CollisionLayer::get_defaults(DrawableType::Pickup);
+
+        assert!(get_can_layers_collide(
+            pickup,
+            CollisionLayer::get_defaults(DrawableType::Player)
+        ));
+
+        for drawable_type in [
+            DrawableType::Enemy(1),
+            DrawableType::Ammunition(1),
+            DrawableType::EnemyAmmunition(1),
+        ] {
+            assert!(!get_can_layers_collide(
+                pickup,
+                CollisionLayer::get_defaults(drawable_type)
+            ));
+        }
+    }
+
+    #[test]
+    fn it_only_collides_enemy_ammunition_with_the_player() {
+        let enemy_ammunition = CollisionLayer::get_defaults(DrawableType::EnemyAmmunition(1));
+
+        assert!(get_can_layers_collide(
+            enemy_ammunition,
+            CollisionLayer::get_defaults(DrawableType::Player)
+        ));
+
+        for drawable_type in [
+            DrawableType::Enemy(1),
+            DrawableType::Ammunition(1),
+            DrawableType::EnemyAmmunition(1),
+        ] {
+            assert!(!get_can_layers_collide(
+                enemy_ammunition,
+                CollisionLayer::get_defaults(drawable_type)
+            ));
+        }
+    }
+}
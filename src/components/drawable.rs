@@ -1,13 +1,22 @@
 use crate::api::display::{Layout, Point};
 use uuid::Uuid;
 
+use super::{CollisionLayer, CollisionMask};
+
 #[derive(Debug, PartialEq)]
 pub struct DrawableState {
     pub layout: Layout,
-    pub location: Point<i64>,
-    pub velocity: Point<i64>,
+    /// Top left of the drawable in sub-cell precision, use get_location for the cell it is drawn in
+    pub location: Point<f64>,
+    /// Location before the last move, collision detection sweeps between this and the location so fast drawables can't pass through others
+    pub previous_location: Point<f64>,
+    /// Cells per second
+    pub velocity: Point<f64>,
     pub drawable_type: DrawableType,
     pub uuid: Uuid,
+    pub collision_layer: CollisionLayer,
+    /// The layers this drawable collides with
+    pub collision_mask: CollisionMask,
 }
 
 #[derive(Debug, PartialEq, Clone, Copy)]
@@ -16,8 +25,29 @@ pub enum DrawableType {
     // Damage of enemy
     Enemy(u32),
     Border,
+    // Purely visual, ignored by collision detection
+    Background,
     // Damage of ammunition
     Ammunition(u32),
+    // Damage of ammunition fired by enemies
+    EnemyAmmunition(u32),
+    // Collected by the player, it deals no damage
+    Pickup,
+}
+
+impl DrawableType {
+    /// The damage the drawable deals on contact
+    pub fn get_damage(&self) -> u32 {
+        match self {
+            DrawableType::Enemy(damage)
+            | DrawableType::Ammunition(damage)
+            | DrawableType::EnemyAmmunition(damage) => *damage,
+            DrawableType::Player
+            | DrawableType::Border
+            | DrawableType::Background
+            | DrawableType::Pickup => 0,
+        }
+    }
 }
 
 impl DrawableState {
@@ -25,91 +55,106 @@ impl DrawableState {
         layout: Layout,
         location: Point<i64>,
         drawable_type: DrawableType,
-        velocity: Option<Point<i64>>,
+        velocity: Option<Point<f64>>,
     ) -> Self {
+        let (collision_layer, collision_mask) = CollisionLayer::get_defaults(drawable_type);
+
         Self {
             layout,
-            location,
+            location: location.into(),
+            previous_location: location.into(),
             drawable_type,
-            velocity: velocity.unwrap_or(Default::default()),
+            velocity: velocity.unwrap_or_default(),
             uuid: Uuid::new_v4(),
+            collision_layer,
+            collision_mask,
         }
     }
-}
 
-const PADDING_OFFSET: i64 = 1;
+    /// Moves the drawable, remembering where it moved from
+    pub fn set_location(&mut self, location: Point<f64>) -> &mut Self {
+        self.previous_location = self.location;
+        self.location = location;
 
-pub fn get_rated_velocity(velocity: Point<i64>, game_loop_duration: u128) -> Point<i64> {
-    // RUST IS SO CLEAN, this seamless cast from i64 to point with .into();
-    let rated_velocity = velocity * game_loop_duration.into() / (1000 as i64).into();
+        self
+    }
 
-    // Use a minimum velocity of 1 if we round to 0
-    fn get_safe_rated_value(rated_value: i64, provided_value: i64) -> i64 {
-        if rated_value != 0 || provided_value == 0 {
-            return rated_value;
-        }
+    /// Moves the drawable without a path from its current location, so nothing between the two locations can be collided with
+    pub fn teleport(&mut self, location: Point<f64>) -> &mut Self {
+        self.previous_location = location;
+        self.location = location;
 
-        if provided_value > 0 {
-            return 1;
-        }
+        self
+    }
 
-        -1
+    /// Returns the cell the drawable was in before the last move
+    pub fn get_previous_location(&self) -> Point<i64> {
+        self.previous_location.round()
     }
 
-    Point {
-        width: get_safe_rated_value(rated_velocity.width, velocity.width),
-        height: get_safe_rated_value(rated_velocity.height, velocity.height),
+    /// Returns the cell the top left of the drawable is in, this is what drawing and collision detection use
+    pub fn get_location(&self) -> Point<i64> {
+        self.location.round()
     }
+
+    /// Returns the middle of the drawable's layout in sub-cell precision
+    pub fn get_center(&self) -> Point<f64> {
+        self.location + Point::from(self.layout.dimensions) / Point::new(2.0, 2.0)
+    }
+}
+
+/// Gap kept between the player and the edges of the world
+pub const PADDING_OFFSET: i64 = 1;
+
+/// Returns how far the velocity moves a drawable over the game loop, this is kept in sub-cell precision so slow drawables still move at the right speed
+pub fn get_rated_velocity(velocity: Point<f64>, game_loop_duration: u128) -> Point<f64> {
+    let seconds = game_loop_duration as f64 / 1000.0;
+
+    velocity * Point::new(seconds, seconds)
 }
 
 pub trait Drawable {
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self;
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self;
 
     fn get_drawable_state(&self) -> &DrawableState;
 
-    fn update_position(
-        &mut self,
-        dimensions: Option<&Point<i64>>,
-        game_loop_duration: u128,
-    ) -> &mut Self {
+    /// Moves the drawable by its velocity
+    fn update_position(&mut self, game_loop_duration: u128) -> &mut Self {
         let drawable_state = self.get_drawable_state();
 
-        // Convert location to i64 so we can handle an underflow of the position; if the position goes below (0,0)
-        let converted_location: Point<i64> = drawable_state.location.into();
+        let updated_position = drawable_state.location
+            + get_rated_velocity(drawable_state.velocity, game_loop_duration);
 
-        let mut updated_position: Point<i64> =
-            converted_location + get_rated_velocity(drawable_state.velocity, game_loop_duration);
-
-        // Block the player from moving outside the boundary
-        if drawable_state.drawable_type == DrawableType::Player {
-            if dimensions.is_none() {
-                panic!("Missing required dimensions for rendering player");
-            }
-
-            if updated_position.height < PADDING_OFFSET {
-                updated_position.height = PADDING_OFFSET;
-            }
+        self.set_position(updated_position)
+    }
+}
 
-            if updated_position.width < PADDING_OFFSET {
-                updated_position.width = PADDING_OFFSET;
-            }
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        entities::{Asteroid, AsteroidSize},
+    };
 
-            let dimensions = dimensions.unwrap();
+    use super::Drawable;
 
-            let drawable_dimensions = drawable_state.layout.dimensions;
+    #[test]
+    fn it_moves_the_same_distance_regardless_of_the_game_loop_duration() {
+        let velocity = Point::new(0.0, 2.0);
 
-            let max_height = dimensions.height - drawable_dimensions.height - PADDING_OFFSET;
-            let max_width = dimensions.width - drawable_dimensions.width - PADDING_OFFSET;
+        let mut slow_frames = Asteroid::new(AsteroidSize::Medium, Point::new(0, 0), velocity);
+        let mut fast_frames = Asteroid::new(AsteroidSize::Medium, Point::new(0, 0), velocity);
 
-            if updated_position.height > max_height {
-                updated_position.height = max_height;
-            }
+        for _ in 0..10 {
+            slow_frames.update_position(100);
+        }
 
-            if updated_position.width > max_width {
-                updated_position.width = max_width;
-            }
+        for _ in 0..100 {
+            fast_frames.update_position(10);
         }
 
-        self.set_position(updated_position.into())
+        // 2 cells per second for a second, fast frames would previously move at least a cell every loop
+        assert_eq!(slow_frames.drawable.get_location(), Point::new(0, 2));
+        assert_eq!(fast_frames.drawable.get_location(), Point::new(0, 2));
     }
 }
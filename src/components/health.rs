@@ -1,7 +1,3 @@
-struct HasHealth {
-    health: u32,
-}
-
 pub fn get_updated_health(mut health: u32, damage: u32) -> u32 {
     if health < damage {
         health = 0;
@@ -1,7 +1,9 @@
+mod collision_layer;
 mod drawable;
 mod health;
-mod spawnable;
+mod steering;
 
+pub use collision_layer::*;
 pub use drawable::*;
 pub use health::*;
-pub use spawnable::*;
+pub use steering::*;
@@ -0,0 +1,204 @@
+use crate::api::display::Point;
+
+use super::DrawableState;
+
+/// How a steered drawable moves relative to its target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SteeringBehaviour {
+    /// Heads straight for where the target is now
+    Seek,
+    /// Heads straight away from the target
+    Flee,
+    /// Heads for where the target will be, based on its velocity and how long it takes to get there
+    Pursue,
+}
+
+/// Where a steered drawable is heading, in world space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SteeringTarget {
+    pub location: Point<f64>,
+    pub velocity: Point<f64>,
+}
+
+impl From<&DrawableState> for SteeringTarget {
+    /// Targets the center of the drawable
+    fn from(drawable_state: &DrawableState) -> Self {
+        Self {
+            location: drawable_state.get_center(),
+            velocity: drawable_state.velocity,
+        }
+    }
+}
+
+/// Updates a drawable's velocity each loop so it turns towards or away from a target
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Steering {
+    pub behaviour: SteeringBehaviour,
+    /// Speed the drawable travels at in cells per second
+    pub max_speed: f64,
+    /// How quickly the drawable can change direction in radians per second
+    pub turn_rate: f64,
+}
+
+impl Steering {
+    pub fn new(behaviour: SteeringBehaviour, max_speed: f64, turn_rate: f64) -> Self {
+        Self {
+            behaviour,
+            max_speed,
+            turn_rate,
+        }
+    }
+
+    /// Returns the velocity after turning towards the behaviour's desired direction for the duration of the game loop. The turn is limited by the turn rate, the speed is always the max speed
+    ///
+    /// # Arguments
+    ///
+    /// * `drawable_state` - The drawable being steered
+    /// * `target` - The target to steer relative to
+    /// * `game_loop_duration` - How long the loop took in milliseconds
+    pub fn get_steered_velocity(
+        &self,
+        drawable_state: &DrawableState,
+        target: &SteeringTarget,
+        game_loop_duration: u128,
+    ) -> Point<f64> {
+        let location = drawable_state.get_center();
+        let velocity = drawable_state.velocity;
+
+        let desired = match self.behaviour {
+            SteeringBehaviour::Seek => target.location - location,
+            SteeringBehaviour::Flee => location - target.location,
+            SteeringBehaviour::Pursue => {
+                let offset = target.location - location;
+                let lead_time =
+                    offset.width.hypot(offset.height) / self.max_speed.max(f64::EPSILON);
+
+                offset + target.velocity * Point::new(lead_time, lead_time)
+            }
+        };
+
+        // Keep going the same way when on top of the target
+        if desired == Point::default() {
+            return velocity;
+        }
+
+        let desired_angle = desired.height.atan2(desired.width);
+
+        // A stationary drawable can face any direction, so it turns straight to the desired direction
+        let angle = if velocity == Point::default() {
+            desired_angle
+        } else {
+            let current_angle = velocity.height.atan2(velocity.width);
+            let max_turn = self.turn_rate * game_loop_duration as f64 / 1000.0;
+
+            current_angle
+                + get_angle_difference(current_angle, desired_angle).clamp(-max_turn, max_turn)
+        };
+
+        Point::new(angle.cos() * self.max_speed, angle.sin() * self.max_speed)
+    }
+}
+
+/// Returns the target closest to the location, None if there are no targets
+pub fn get_nearest_target(
+    location: Point<f64>,
+    targets: &[SteeringTarget],
+) -> Option<SteeringTarget> {
+    let get_distance = |target: &SteeringTarget| {
+        let offset = target.location - location;
+
+        offset.width.hypot(offset.height)
+    };
+
+    targets
+        .iter()
+        .min_by(|first, second| get_distance(first).total_cmp(&get_distance(second)))
+        .copied()
+}
+
+/// Returns the shortest signed turn from the first angle to the second, between -PI and PI
+fn get_angle_difference(from: f64, to: f64) -> f64 {
+    let difference = (to - from).rem_euclid(std::f64::consts::TAU);
+
+    if difference > std::f64::consts::PI {
+        difference - std::f64::consts::TAU
+    } else {
+        difference
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::{
+        api::display::{Layout, Point},
+        components::{DrawableState, DrawableType},
+    };
+
+    use super::{Steering, SteeringBehaviour, SteeringTarget};
+
+    fn create_drawable(velocity: Point<f64>) -> DrawableState {
+        // A single cell at the origin, its center is (0.5, 0.5)
+        DrawableState::new(
+            Layout::from_ascii("*", Color::White),
+            Point::new(0, 0),
+            DrawableType::Ammunition(1),
+            Some(velocity),
+        )
+    }
+
+    fn create_target(location: Point<f64>, velocity: Point<f64>) -> SteeringTarget {
+        SteeringTarget { location, velocity }
+    }
+
+    #[test]
+    fn it_seeks_and_flees_the_target() {
+        let drawable = create_drawable(Point::default());
+        let target = create_target(Point::new(10.5, 0.5), Point::default());
+
+        let seek = Steering::new(SteeringBehaviour::Seek, 10.0, 1.0);
+
+        assert_eq!(
+            seek.get_steered_velocity(&drawable, &target, 16).round(),
+            Point::new(10, 0)
+        );
+
+        let flee = Steering::new(SteeringBehaviour::Flee, 10.0, 1.0);
+
+        assert_eq!(
+            flee.get_steered_velocity(&drawable, &target, 16).round(),
+            Point::new(-10, 0)
+        );
+    }
+
+    #[test]
+    fn it_leads_a_moving_target_when_pursuing() {
+        let drawable = create_drawable(Point::default());
+        let target = create_target(Point::new(10.5, 0.5), Point::new(0.0, 10.0));
+
+        let velocity = Steering::new(SteeringBehaviour::Pursue, 10.0, 1.0)
+            .get_steered_velocity(&drawable, &target, 16);
+
+        // The target takes a second to reach, by then it has moved down by 10, so the aim is half way between
+        assert_eq!(velocity.round(), Point::new(7, 7));
+    }
+
+    #[test]
+    fn it_limits_how_quickly_it_turns() {
+        let drawable = create_drawable(Point::new(10.0, 0.0));
+        let target = create_target(Point::new(0.5, 10.5), Point::default());
+
+        let steering = Steering::new(SteeringBehaviour::Seek, 10.0, std::f64::consts::FRAC_PI_2);
+
+        // Half a second only turns a quarter of the way round to face straight down
+        let velocity = steering.get_steered_velocity(&drawable, &target, 500);
+        let angle = velocity.height.atan2(velocity.width);
+
+        assert!((angle - std::f64::consts::FRAC_PI_4).abs() < 1e-9);
+
+        let velocity = steering.get_steered_velocity(&drawable, &target, 2000);
+
+        assert_eq!(velocity.round(), Point::new(0, 10));
+    }
+}
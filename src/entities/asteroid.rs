@@ -1,38 +1,185 @@
 use crossterm::style::Color;
+use rand::Rng;
 
 use crate::{
     api::display::{Layout, Point},
     components::{get_updated_health, Drawable, DrawableState, DrawableType, Health},
 };
 
-use super::consts::ASTEROID;
+use super::{
+    consts::{LARGE_ASTEROID, MEDIUM_ASTEROID, SMALL_ASTEROID},
+    generate_asteroid_layout,
+};
+
+/// Angle in radians each fragment can turn away from the direction the destroyed asteroid was travelling
+const FRAGMENT_SPREAD: f64 = std::f64::consts::FRAC_PI_3;
+/// Fragments travel this much faster than the asteroid they broke off from
+const FRAGMENT_SPEED_MULTIPLIER: f64 = 1.3;
+/// Fragments of an asteroid that wasn't moving still fly apart at this speed in cells per second
+const FRAGMENT_MIN_SPEED: f64 = 8.0;
+
+/// Asteroids break down from large to medium to small, small asteroids are destroyed outright
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsteroidSize {
+    Large,
+    Medium,
+    Small,
+}
+
+impl AsteroidSize {
+    fn get_sprite(&self) -> &'static str {
+        match self {
+            AsteroidSize::Large => LARGE_ASTEROID,
+            AsteroidSize::Medium => MEDIUM_ASTEROID,
+            AsteroidSize::Small => SMALL_ASTEROID,
+        }
+    }
+
+    /// Radius in rows of generated asteroids of the size
+    fn get_radius(&self) -> i64 {
+        match self {
+            AsteroidSize::Large => 3,
+            AsteroidSize::Medium => 2,
+            AsteroidSize::Small => 1,
+        }
+    }
+
+    pub fn get_health(&self) -> u32 {
+        match self {
+            AsteroidSize::Large => 3,
+            AsteroidSize::Medium => 2,
+            AsteroidSize::Small => 1,
+        }
+    }
+
+    /// Damage dealt to the player on contact
+    pub fn get_damage(&self) -> u32 {
+        match self {
+            AsteroidSize::Large => 2,
+            AsteroidSize::Medium | AsteroidSize::Small => 1,
+        }
+    }
+
+    /// Points for destroying the asteroid, smaller asteroids are harder to hit so they are worth more
+    pub fn get_points(&self) -> u64 {
+        match self {
+            AsteroidSize::Large => 1,
+            AsteroidSize::Medium => 2,
+            AsteroidSize::Small => 3,
+        }
+    }
 
-pub const ASTEROID_DAMAGE: u32 = 1;
+    /// The size of the fragments the asteroid breaks into, None if it doesn't break up
+    pub fn get_fragment_size(&self) -> Option<AsteroidSize> {
+        match self {
+            AsteroidSize::Large => Some(AsteroidSize::Medium),
+            AsteroidSize::Medium => Some(AsteroidSize::Small),
+            AsteroidSize::Small => None,
+        }
+    }
+}
 
 pub struct Asteroid {
     pub drawable: DrawableState,
     pub health: u32,
+    pub size: AsteroidSize,
+    // Whether the shape was generated rather than using the fixed sprite, fragments keep the same style
+    is_generated: bool,
 }
 
 impl Asteroid {
-    pub fn new(location: Point<i64>, velocity: Point<i64>) -> Self {
-        let map = Layout::from_ascii(ASTEROID, Color::Grey);
+    /// Creates an asteroid with the fixed sprite for its size
+    pub fn new(size: AsteroidSize, location: Point<i64>, velocity: Point<f64>) -> Self {
+        Self::from_layout(
+            size,
+            Layout::from_ascii(size.get_sprite(), Color::Grey),
+            location,
+            velocity,
+            false,
+        )
+    }
 
+    /// Creates an asteroid with a randomly generated shape for its size
+    pub fn generate(
+        size: AsteroidSize,
+        location: Point<i64>,
+        velocity: Point<f64>,
+        rng: &mut impl Rng,
+    ) -> Self {
+        Self::from_layout(
+            size,
+            generate_asteroid_layout(size.get_radius(), rng),
+            location,
+            velocity,
+            true,
+        )
+    }
+
+    fn from_layout(
+        size: AsteroidSize,
+        layout: Layout,
+        location: Point<i64>,
+        velocity: Point<f64>,
+        is_generated: bool,
+    ) -> Self {
         Self {
             drawable: DrawableState::new(
-                map,
+                layout,
                 location,
-                DrawableType::Enemy(ASTEROID_DAMAGE),
+                DrawableType::Enemy(size.get_damage()),
                 Some(velocity),
             ),
-            health: 1,
+            health: size.get_health(),
+            size,
+            is_generated,
         }
     }
+
+    /// Builds the two or three fragments the asteroid breaks into when destroyed, generated asteroids break into fragments with generated shapes. The fragments start around the asteroid's center and fly apart from the direction it was travelling in
+    pub fn build_fragments(&self, rng: &mut impl Rng) -> Vec<Asteroid> {
+        let Some(fragment_size) = self.size.get_fragment_size() else {
+            return vec![];
+        };
+
+        let fragment_count: usize = rng.gen_range(2..=3);
+
+        let velocity = self.drawable.velocity;
+        let speed = (velocity.width.hypot(velocity.height) * FRAGMENT_SPEED_MULTIPLIER)
+            .max(FRAGMENT_MIN_SPEED);
+        let base_angle = velocity.height.atan2(velocity.width);
+
+        (0..fragment_count)
+            .map(|index| {
+                // Spread the fragments evenly across the spread so they diverge, with a little randomness
+                let position = index as f64 / (fragment_count - 1) as f64;
+                let angle = base_angle
+                    + FRAGMENT_SPREAD * (position * 2.0 - 1.0)
+                    + rng.gen_range(-0.1..=0.1);
+
+                let fragment_velocity = Point::new(angle.cos() * speed, angle.sin() * speed);
+
+                let mut fragment = if self.is_generated {
+                    Asteroid::generate(fragment_size, Point::default(), fragment_velocity, rng)
+                } else {
+                    Asteroid::new(fragment_size, Point::default(), fragment_velocity)
+                };
+
+                let half_dimensions =
+                    Point::from(fragment.drawable.layout.dimensions) / Point::new(2.0, 2.0);
+
+                fragment
+                    .drawable
+                    .teleport(self.drawable.get_center() - half_dimensions);
+
+                fragment
+            })
+            .collect()
+    }
 }
 
 impl Drawable for Asteroid {
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
-        self.drawable.location = updated_position;
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.set_location(updated_position);
 
         self
     }
@@ -53,3 +200,43 @@ impl Health for Asteroid {
         self.health
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::api::display::Point;
+
+    use super::{Asteroid, AsteroidSize};
+
+    #[test]
+    fn it_breaks_into_smaller_diverging_fragments() {
+        let asteroid = Asteroid::new(
+            AsteroidSize::Large,
+            Point::new(20, 20),
+            Point::new(0.0, 10.0),
+        );
+
+        let mut rng = StdRng::seed_from_u64(1);
+
+        let fragments = asteroid.build_fragments(&mut rng);
+
+        assert!(fragments.len() == 2 || fragments.len() == 3);
+        assert!(fragments
+            .iter()
+            .all(|fragment| fragment.size == AsteroidSize::Medium));
+
+        // The outer fragments head off on opposite sides of the original direction
+        let first = fragments.first().unwrap().drawable.velocity;
+        let last = fragments.last().unwrap().drawable.velocity;
+
+        assert!(first.height > 0.0 && last.height > 0.0);
+        assert!(first.width * last.width < 0.0);
+
+        assert!(
+            Asteroid::new(AsteroidSize::Small, Point::new(20, 20), Point::default())
+                .build_fragments(&mut rng)
+                .is_empty()
+        );
+    }
+}
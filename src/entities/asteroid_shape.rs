@@ -0,0 +1,152 @@
+use crossterm::style::Color;
+use rand::Rng;
+
+use crate::api::display::{
+    element::{BlendMode, DEFAULT_BACKGROUND},
+    Element, Layout, Point,
+};
+
+/// Characters used for the outline of an asteroid
+const ROCK_EDGE_CHARACTERS: [char; 5] = ['#', '%', '@', '&', '$'];
+/// Characters used for the inside of an asteroid, craters and texture
+const ROCK_FILL_CHARACTERS: [char; 5] = ['.', ':', 'o', '*', '+'];
+/// Colours the rock is shaded with
+const ROCK_SHADES: [Color; 3] = [Color::Grey, Color::DarkGrey, Color::White];
+
+/// Number of points around the asteroid with their own radius, the outline is interpolated between them
+const OUTLINE_POINTS: usize = 8;
+/// Smallest fraction of the radius a point on the outline can be pulled in by, lower values make more jagged asteroids
+const MIN_OUTLINE_SCALE: f64 = 0.7;
+
+/// Cells are roughly twice as tall as they are wide, so asteroids are twice as wide in cells to look round
+const CELL_ASPECT_RATIO: f64 = 2.0;
+
+/// Generates a random, roughly round asteroid. Only the cells inside the outline are filled so the collision mask matches the visible shape
+///
+/// # Arguments
+///
+/// * `radius` - Radius of the asteroid in rows, the asteroid is twice as many columns wide
+/// * `rng` - Source of randomness, a seeded generator always produces the same asteroid
+pub fn generate_asteroid_layout(radius: i64, rng: &mut impl Rng) -> Layout {
+    let radius = radius.max(1);
+
+    let outline: Vec<f64> = (0..OUTLINE_POINTS)
+        .map(|_| radius as f64 * rng.gen_range(MIN_OUTLINE_SCALE..=1.0))
+        .collect();
+
+    let dimensions = Point::new(
+        (radius as f64 * 2.0 * CELL_ASPECT_RATIO) as i64 + 1,
+        radius * 2 + 1,
+    );
+    let center = Point::new(
+        (dimensions.width / 2) as f64,
+        (dimensions.height / 2) as f64,
+    );
+
+    let is_inside = |column: i64, row: i64| -> bool {
+        if column < 0 || row < 0 || column >= dimensions.width || row >= dimensions.height {
+            return false;
+        }
+
+        let offset = Point::new(
+            (column as f64 - center.width) / CELL_ASPECT_RATIO,
+            row as f64 - center.height,
+        );
+
+        offset.width.hypot(offset.height) <= get_outline_radius(&outline, offset) + 0.25
+    };
+
+    let mut layout = Layout::new(&dimensions, None);
+
+    for row in 0..dimensions.height {
+        for column in 0..dimensions.width {
+            if !is_inside(column, row) {
+                continue;
+            }
+
+            let is_edge =
+                [(-1, 0), (1, 0), (0, -1), (0, 1)]
+                    .iter()
+                    .any(|(column_offset, row_offset)| {
+                        !is_inside(column + column_offset, row + row_offset)
+                    });
+
+            let characters = if is_edge {
+                &ROCK_EDGE_CHARACTERS
+            } else {
+                &ROCK_FILL_CHARACTERS
+            };
+
+            let element = Element::new(
+                characters[rng.gen_range(0..characters.len())],
+                DEFAULT_BACKGROUND,
+                ROCK_SHADES[rng.gen_range(0..ROCK_SHADES.len())],
+            )
+            .with_blend_mode(BlendMode::KeepBackground);
+
+            // The cell is always within the layout
+            let _ = layout.draw_item(element, &Point::new(column, row));
+        }
+    }
+
+    layout
+}
+
+/// Returns the radius of the outline in the direction of the offset, interpolating between the outline points either side
+fn get_outline_radius(outline: &[f64], offset: Point<f64>) -> f64 {
+    let turn = offset.height.atan2(offset.width) / std::f64::consts::TAU;
+    let position = turn.rem_euclid(1.0) * outline.len() as f64;
+
+    let index = position.floor() as usize % outline.len();
+    let next_index = (index + 1) % outline.len();
+    let fraction = position.fract();
+
+    outline[index] * (1.0 - fraction) + outline[next_index] * fraction
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::api::display::Point;
+
+    use super::generate_asteroid_layout;
+
+    #[test]
+    fn it_generates_the_same_asteroid_for_the_same_seed() {
+        let first = generate_asteroid_layout(3, &mut StdRng::seed_from_u64(7));
+        let second = generate_asteroid_layout(3, &mut StdRng::seed_from_u64(7));
+        let other = generate_asteroid_layout(3, &mut StdRng::seed_from_u64(8));
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn it_generates_a_solid_round_asteroid_of_the_radius() {
+        let layout = generate_asteroid_layout(2, &mut StdRng::seed_from_u64(1));
+
+        assert_eq!(layout.dimensions, Point::new(9, 5));
+
+        // The center is always filled and the corners are always empty
+        assert!(layout.map[2][4].is_some());
+
+        for (column, row) in [(0, 0), (8, 0), (0, 4), (8, 4)] {
+            assert!(layout.map[row][column].is_none());
+        }
+
+        // Every filled row is a single run of cells, so there are no gaps in the rock
+        for row in layout.map.iter() {
+            let filled: Vec<usize> = row
+                .iter()
+                .enumerate()
+                .filter(|(_, element)| element.is_some())
+                .map(|(index, _)| index)
+                .collect();
+
+            if let (Some(first), Some(last)) = (filled.first(), filled.last()) {
+                assert_eq!(last - first + 1, filled.len());
+            }
+        }
+    }
+}
@@ -1,5 +1,4 @@
 use crossterm::style::Color;
-use uuid::Uuid;
 
 use crate::{
     api::display::{element::DEFAULT_BACKGROUND, DisplayControllerError, Element, Layout, Point},
@@ -13,13 +12,12 @@ pub struct Borders {
 
 impl Borders {
     pub fn new(dimensions: &Point<i64>) -> Result<Self, DisplayControllerError> {
-        let mut drawable = DrawableState {
-            layout: Layout::new(dimensions, None),
-            location: Point::default(),
-            velocity: Default::default(),
-            drawable_type: DrawableType::Border,
-            uuid: Uuid::new_v4(),
-        };
+        let mut drawable = DrawableState::new(
+            Layout::new(dimensions, None),
+            Point::default(),
+            DrawableType::Border,
+            None,
+        );
 
         drawable.layout.draw_rect(
             &Default::default(),
@@ -36,8 +34,8 @@ impl Drawable for Borders {
         &self.drawable
     }
 
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
-        self.drawable.location = updated_position;
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.set_location(updated_position);
 
         self
     }
@@ -0,0 +1,467 @@
+use std::collections::HashSet;
+
+use crossterm::style::Color;
+
+use crate::{
+    api::display::{map_from_str, Layout, Point},
+    components::{get_updated_health, Drawable, DrawableState, DrawableType, Health},
+};
+
+use super::{
+    consts::{BOSS_CORE, BOSS_HULL, BOSS_TURRET},
+    Bullet,
+};
+
+pub const BOSS_HEALTH: u32 = 30;
+pub const BOSS_DAMAGE: u32 = 3;
+
+/// Speed the boss flies into the world at, in cells per second
+const BOSS_ENTRY_SPEED: f64 = 5.0;
+/// The boss stops entering once its top reaches this row of the world
+const BOSS_HOLD_HEIGHT: f64 = 2.0;
+/// Angle in radians between the bullets of a spread
+const BOSS_SPREAD_ANGLE: f64 = 0.3;
+
+/// How the boss moves once it has entered the world
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossMovement {
+    /// Stays still
+    Hover,
+    /// Moves side to side at the speed in cells per second, turning around at the edges of the world
+    Sweep(f64),
+    /// Follows the target from side to side at up to the speed in cells per second
+    Chase(f64),
+}
+
+/// What the boss fires from each of its turrets
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossAttack {
+    /// A single bullet at the target
+    Aimed,
+    /// A fan of bullets spreading downwards
+    Spread(u32),
+}
+
+/// A stage of the fight, each phase starts once the boss's health drops to its fraction of the max health
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BossPhase {
+    pub health_fraction: f64,
+    pub movement: BossMovement,
+    pub attack: BossAttack,
+    /// Time between attacks in milliseconds
+    pub fire_rate: u128,
+}
+
+/// Part of the boss's sprite, the boss is built up from its parts
+struct BossPart {
+    ascii: &'static str,
+    offset: Point<i64>,
+    color: Color,
+    /// Only the cells of weak points take damage
+    is_weak_point: bool,
+    /// Bullets are fired from the bottom middle of guns
+    is_gun: bool,
+}
+
+const BOSS_PARTS: [BossPart; 4] = [
+    BossPart {
+        ascii: BOSS_HULL,
+        offset: Point::new(0, 0),
+        color: Color::DarkGrey,
+        is_weak_point: false,
+        is_gun: false,
+    },
+    BossPart {
+        ascii: BOSS_TURRET,
+        offset: Point::new(5, 3),
+        color: Color::Grey,
+        is_weak_point: false,
+        is_gun: true,
+    },
+    BossPart {
+        ascii: BOSS_TURRET,
+        offset: Point::new(13, 3),
+        color: Color::Grey,
+        is_weak_point: false,
+        is_gun: true,
+    },
+    // The hull is open beneath the core so shots from below can reach it
+    BossPart {
+        ascii: BOSS_CORE,
+        offset: Point::new(8, 1),
+        color: Color::Red,
+        is_weak_point: true,
+        is_gun: false,
+    },
+];
+
+/// The phases every boss goes through, it speeds up and fires more as it is worn down
+const BOSS_PHASES: [BossPhase; 3] = [
+    BossPhase {
+        health_fraction: 1.0,
+        movement: BossMovement::Hover,
+        attack: BossAttack::Aimed,
+        fire_rate: 1200,
+    },
+    BossPhase {
+        health_fraction: 0.6,
+        movement: BossMovement::Sweep(10.0),
+        attack: BossAttack::Spread(3),
+        fire_rate: 1000,
+    },
+    BossPhase {
+        health_fraction: 0.3,
+        movement: BossMovement::Chase(15.0),
+        attack: BossAttack::Spread(5),
+        fire_rate: 700,
+    },
+];
+
+/// A large enemy made of several parts, it can only be damaged through its weak points and changes how it fights as its health drops
+pub struct Boss {
+    pub drawable: DrawableState,
+    pub health: u32,
+    pub max_health: u32,
+    phases: Vec<BossPhase>,
+    phase_index: usize,
+    // Cells relative to the top left of the boss that take damage
+    weak_points: HashSet<Point<i64>>,
+    // Locations relative to the top left of the boss that bullets are fired from
+    gun_offsets: Vec<Point<i64>>,
+    fire_time_remaining: u128,
+    // 1 while sweeping right and -1 while sweeping left
+    sweep_direction: f64,
+}
+
+impl Boss {
+    /// Creates a boss at the top of the world at the horizontal location with only its bottom row showing, it flies down into the world before fighting
+    pub fn new(location: Point<i64>) -> Self {
+        let hull_dimensions = Layout::from_ascii(BOSS_HULL, Color::DarkGrey).dimensions;
+
+        let mut layout = Layout::new(&hull_dimensions, None);
+        let mut weak_points = HashSet::new();
+        let mut gun_offsets = vec![];
+
+        for part in BOSS_PARTS.iter() {
+            let map = map_from_str(part.ascii, part.color);
+
+            // Parts always fit within the hull
+            let _ = layout.draw_map(&map, part.offset, &Point::default());
+
+            if part.is_weak_point {
+                for (row, elements) in map.iter().enumerate() {
+                    for (column, element) in elements.iter().enumerate() {
+                        if element.is_some() {
+                            weak_points.insert(part.offset + Point::new(column as i64, row as i64));
+                        }
+                    }
+                }
+            }
+
+            if part.is_gun {
+                gun_offsets
+                    .push(part.offset + Point::new(map[0].len() as i64 / 2, map.len() as i64));
+            }
+        }
+
+        let phases = BOSS_PHASES.to_vec();
+
+        Self {
+            drawable: DrawableState::new(
+                layout,
+                Point::new(location.width, 1 - hull_dimensions.height),
+                DrawableType::Enemy(BOSS_DAMAGE),
+                Some(Point::new(0.0, BOSS_ENTRY_SPEED)),
+            ),
+            health: BOSS_HEALTH,
+            max_health: BOSS_HEALTH,
+            fire_time_remaining: phases[0].fire_rate,
+            phases,
+            phase_index: 0,
+            weak_points,
+            gun_offsets,
+            sweep_direction: 1.0,
+        }
+    }
+
+    pub fn get_phase_index(&self) -> usize {
+        self.phase_index
+    }
+
+    /// Returns the damage the boss takes from a hit on the world cells, hits that miss every weak point do no damage
+    pub fn get_weak_point_damage(&self, cells: &[Point<i64>], damage: u32) -> u32 {
+        let location = self.drawable.get_location();
+
+        if cells
+            .iter()
+            .any(|cell| self.weak_points.contains(&(*cell - location)))
+        {
+            damage
+        } else {
+            0
+        }
+    }
+
+    /// Moves the boss for its phase and fires when its attack is ready
+    ///
+    /// # Arguments
+    ///
+    /// * `game_loop_duration` - How long the loop took in milliseconds
+    /// * `world_dimensions` - The boss sweeps between the edges of the world
+    /// * `target` - World location the boss fires at and chases, None holds its fire
+    ///
+    /// # Returns
+    ///
+    /// * The bullets fired this loop
+    pub fn handle_game_loop(
+        &mut self,
+        game_loop_duration: u128,
+        world_dimensions: Point<i64>,
+        target: Option<Point<i64>>,
+    ) -> Vec<Bullet> {
+        // The boss doesn't fight until it has fully entered the world
+        if self.drawable.location.height < BOSS_HOLD_HEIGHT {
+            self.drawable.velocity = Point::new(0.0, BOSS_ENTRY_SPEED);
+
+            return vec![];
+        }
+
+        let phase = self.phases[self.phase_index];
+
+        self.drawable.velocity = Point::new(
+            self.get_horizontal_speed(phase.movement, world_dimensions, target),
+            0.0,
+        );
+
+        self.fire_time_remaining = self.fire_time_remaining.saturating_sub(game_loop_duration);
+
+        if self.fire_time_remaining > 0 {
+            return vec![];
+        }
+
+        let Some(target) = target else {
+            return vec![];
+        };
+
+        self.fire_time_remaining = phase.fire_rate;
+
+        let location = self.drawable.get_location();
+
+        self.gun_offsets
+            .iter()
+            .flat_map(|gun_offset| {
+                let gun = location + *gun_offset;
+
+                match phase.attack {
+                    BossAttack::Aimed => vec![Bullet::build_enemy_bullet(gun, target)],
+                    BossAttack::Spread(count) => (0..count)
+                        .map(|index| {
+                            let angle = std::f64::consts::FRAC_PI_2
+                                + (index as f64 - (count - 1) as f64 / 2.0) * BOSS_SPREAD_ANGLE;
+
+                            let direction = Point::new(angle.cos() * 10.0, angle.sin() * 10.0);
+
+                            Bullet::build_enemy_bullet(gun, gun + direction.round())
+                        })
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    fn get_horizontal_speed(
+        &mut self,
+        movement: BossMovement,
+        world_dimensions: Point<i64>,
+        target: Option<Point<i64>>,
+    ) -> f64 {
+        match movement {
+            BossMovement::Hover => 0.0,
+            BossMovement::Sweep(speed) => {
+                let left = self.drawable.location.width;
+                let right = left + self.drawable.layout.dimensions.width as f64;
+
+                if left <= 0.0 {
+                    self.sweep_direction = 1.0;
+                } else if right >= world_dimensions.width as f64 {
+                    self.sweep_direction = -1.0;
+                }
+
+                speed * self.sweep_direction
+            }
+            BossMovement::Chase(speed) => match target {
+                Some(target) => {
+                    let offset = target.width as f64 - self.drawable.get_center().width;
+
+                    offset.clamp(-speed, speed)
+                }
+                None => 0.0,
+            },
+        }
+    }
+
+    /// Moves to the latest phase whose health threshold has been reached
+    fn update_phase(&mut self) {
+        let health_fraction = self.health as f64 / self.max_health.max(1) as f64;
+
+        if let Some(index) = self
+            .phases
+            .iter()
+            .rposition(|phase| health_fraction <= phase.health_fraction)
+        {
+            if index != self.phase_index {
+                self.phase_index = index;
+                self.fire_time_remaining = self.phases[index].fire_rate;
+            }
+        }
+    }
+}
+
+impl Drawable for Boss {
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.set_location(updated_position);
+
+        self
+    }
+
+    fn get_drawable_state(&self) -> &DrawableState {
+        &self.drawable
+    }
+}
+
+impl Health for Boss {
+    fn apply_damage(&mut self, damage: u32) -> &mut Self {
+        self.health = get_updated_health(self.health, damage);
+
+        self.update_phase();
+
+        self
+    }
+
+    fn get_health(&self) -> u32 {
+        self.health
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        components::{Drawable, Health},
+        entities::Bullet,
+        systems::{get_collision_events, run_collision_detection, CollisionResponseTable},
+    };
+
+    use super::{Boss, BossAttack, BOSS_HEALTH, BOSS_PHASES};
+
+    const WORLD_DIMENSIONS: Point<i64> = Point::new(100, 40);
+
+    fn create_entered_boss() -> Boss {
+        let mut boss = Boss::new(Point::new(10, 0));
+
+        boss.drawable.teleport(Point::new(10.0, 5.0));
+
+        boss
+    }
+
+    #[test]
+    fn it_only_takes_damage_on_weak_points() {
+        let boss = create_entered_boss();
+
+        // The hull's top left corner is armour, the core is in the middle of the boss
+        assert_eq!(boss.get_weak_point_damage(&[Point::new(12, 5)], 5), 0);
+        assert_eq!(
+            boss.get_weak_point_damage(&[Point::new(12, 5), Point::new(19, 6)], 5),
+            5
+        );
+    }
+
+    #[test]
+    fn it_takes_damage_from_bullets_fired_up_into_the_core() {
+        let collision_responses = CollisionResponseTable::default();
+
+        for game_loop_duration in [16, 50, 100] {
+            let mut boss = create_entered_boss();
+            // The bullet's sprite is centered on the column above its location
+            let mut bullet = Bullet::build_basic_bullet(Point::new(19, 30));
+
+            for _ in 0..100 {
+                bullet.update_position(game_loop_duration);
+
+                let collision_results = run_collision_detection(
+                    vec![&boss.drawable, &bullet.drawable],
+                    &WORLD_DIMENSIONS,
+                    &collision_responses,
+                );
+                let collision_events = get_collision_events(&collision_results);
+
+                for collision_event in collision_events.iter() {
+                    let damage = if collision_event.affected_uuid == boss.drawable.uuid {
+                        collision_event.affected_damage
+                    } else {
+                        collision_event.other_damage
+                    };
+
+                    boss.apply_damage(boss.get_weak_point_damage(&collision_event.cells, damage));
+                }
+
+                // The bullet is destroyed by whatever it hits first
+                if !collision_events.is_empty() {
+                    break;
+                }
+            }
+
+            assert!(boss.get_health() < BOSS_HEALTH);
+        }
+    }
+
+    #[test]
+    fn it_changes_phase_at_health_thresholds() {
+        let mut boss = create_entered_boss();
+
+        assert_eq!(boss.get_phase_index(), 0);
+
+        let damage_to_second_phase =
+            BOSS_HEALTH - (BOSS_HEALTH as f64 * BOSS_PHASES[1].health_fraction) as u32;
+
+        boss.apply_damage(damage_to_second_phase - 1);
+
+        assert_eq!(boss.get_phase_index(), 0);
+
+        boss.apply_damage(1);
+
+        assert_eq!(boss.get_phase_index(), 1);
+
+        // The second phase fires a spread from each turret
+        let BossAttack::Spread(count) = BOSS_PHASES[1].attack else {
+            panic!("Expected the second phase to fire a spread");
+        };
+
+        let bullets = boss.handle_game_loop(
+            BOSS_PHASES[1].fire_rate,
+            WORLD_DIMENSIONS,
+            Some(Point::new(20, 30)),
+        );
+
+        assert_eq!(bullets.len(), count as usize * 2);
+
+        boss.apply_damage(BOSS_HEALTH);
+
+        assert_eq!(boss.get_phase_index(), 2);
+    }
+
+    #[test]
+    fn it_enters_the_world_before_fighting() {
+        let mut boss = Boss::new(Point::new(10, 0));
+
+        assert!(boss.drawable.location.height < 0.0);
+        assert!(boss
+            .handle_game_loop(
+                BOSS_PHASES[0].fire_rate,
+                WORLD_DIMENSIONS,
+                Some(Point::new(20, 30))
+            )
+            .is_empty());
+        assert!(boss.drawable.velocity.height > 0.0);
+    }
+}
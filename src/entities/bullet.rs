@@ -1,29 +1,60 @@
 use crossterm::style::Color;
 
 use crate::{
-    api::display::{
-        element::{DEFAULT_BACKGROUND, DEFAULT_FOREGROUND},
-        Element, Layout, Point,
+    api::display::{Layout, Point},
+    components::{
+        get_updated_health, Drawable, DrawableState, DrawableType, Health, Steering,
+        SteeringBehaviour,
     },
-    components::{get_updated_health, Drawable, DrawableState, DrawableType, Health},
 };
 
-use super::consts::{BASIC_BULLET, SPREAD_BULLET};
+use super::consts::{BASIC_BULLET, ENEMY_BULLET, HOMING_MISSILE, SPREAD_BULLET};
 
 pub struct Bullet {
     pub drawable: DrawableState,
     pub health: u32,
+    /// Bullets with steering turn towards a target each loop, others fly straight
+    pub steering: Option<Steering>,
 }
 
 pub const BULLET_DAMAGE: u32 = 1;
 
+/// Speed of a basic bullet in cells per second
+const BASIC_BULLET_SPEED: f64 = 20.0;
+
+pub const HOMING_MISSILE_DAMAGE: u32 = 2;
+
+/// Speed of a homing missile in cells per second
+const HOMING_MISSILE_SPEED: f64 = 18.0;
+/// How quickly a homing missile turns in radians per second
+const HOMING_MISSILE_TURN_RATE: f64 = 3.0;
+
+pub const ENEMY_BULLET_DAMAGE: u32 = 1;
+
+/// Speed of an enemy bullet in cells per second, slower than the player's so they can be dodged
+const ENEMY_BULLET_SPEED: f64 = 12.0;
+
+/// Returns the velocity that travels from the location towards the target at the speed, None if the location is the target
+fn get_aimed_velocity(location: Point<i64>, target: Point<i64>, speed: f64) -> Option<Point<f64>> {
+    let direction: Point<f64> = (target - location).into();
+    let distance = direction.width.hypot(direction.height);
+
+    if distance == 0.0 {
+        return None;
+    }
+
+    let scale = speed / distance;
+
+    Some(direction * Point::new(scale, scale))
+}
+
 impl Bullet {
     pub fn build_basic_bullet(location: Point<i64>) -> Self {
         let map = Layout::from_ascii(BASIC_BULLET, Color::White);
 
-        let velocity: Point<i64> = Point {
-            height: -20,
-            width: 0,
+        let velocity: Point<f64> = Point {
+            height: -BASIC_BULLET_SPEED,
+            width: 0.0,
         };
 
         Self {
@@ -34,15 +65,66 @@ impl Bullet {
                 Some(velocity),
             ),
             health: 1,
+            steering: None,
+        }
+    }
+
+    /// Builds a basic bullet that travels from the location towards the target, the bullet travels straight up if the target is the location
+    pub fn build_aimed_bullet(location: Point<i64>, target: Point<i64>) -> Self {
+        let mut bullet = Self::build_basic_bullet(location);
+
+        if let Some(velocity) = get_aimed_velocity(location, target, BASIC_BULLET_SPEED) {
+            bullet.drawable.velocity = velocity;
+        }
+
+        bullet
+    }
+
+    /// Builds a bullet fired by an enemy that travels from the location towards the target, it only damages the player. The bullet travels straight down if the target is the location
+    pub fn build_enemy_bullet(location: Point<i64>, target: Point<i64>) -> Self {
+        let map = Layout::from_ascii(ENEMY_BULLET, Color::Red);
+
+        let velocity = get_aimed_velocity(location, target, ENEMY_BULLET_SPEED)
+            .unwrap_or(Point::new(0.0, ENEMY_BULLET_SPEED));
+
+        Self {
+            drawable: DrawableState::new(
+                map,
+                location,
+                DrawableType::EnemyAmmunition(ENEMY_BULLET_DAMAGE),
+                Some(velocity),
+            ),
+            health: 1,
+            steering: None,
+        }
+    }
+
+    /// Builds a missile that launches straight up and then pursues the nearest target it is steered towards
+    pub fn build_homing_missile(location: Point<i64>) -> Self {
+        let map = Layout::from_ascii(HOMING_MISSILE, Color::Magenta);
+
+        Self {
+            drawable: DrawableState::new(
+                map,
+                location,
+                DrawableType::Ammunition(HOMING_MISSILE_DAMAGE),
+                Some(Point::new(0.0, -HOMING_MISSILE_SPEED)),
+            ),
+            health: 1,
+            steering: Some(Steering::new(
+                SteeringBehaviour::Pursue,
+                HOMING_MISSILE_SPEED,
+                HOMING_MISSILE_TURN_RATE,
+            )),
         }
     }
 
     pub fn build_spread_bullet(location: Point<i64>) -> Self {
         let map = Layout::from_ascii(SPREAD_BULLET, Color::White);
 
-        let velocity: Point<i64> = Point {
-            height: -10,
-            width: 0,
+        let velocity: Point<f64> = Point {
+            height: -10.0,
+            width: 0.0,
         };
 
         Self {
@@ -53,13 +135,14 @@ impl Bullet {
                 Some(velocity),
             ),
             health: 5,
+            steering: None,
         }
     }
 }
 
 impl Drawable for Bullet {
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
-        self.drawable.location = updated_position;
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.set_location(updated_position);
 
         self
     }
@@ -80,3 +163,28 @@ impl Health for Bullet {
         self.health
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::api::display::Point;
+
+    use super::{Bullet, BASIC_BULLET_SPEED};
+
+    #[test]
+    fn it_aims_bullets_at_the_target() {
+        let bullet = Bullet::build_aimed_bullet(Point::new(10, 10), Point::new(13, 14));
+
+        // A 3-4-5 triangle scaled up to the bullet speed
+        assert_eq!(
+            bullet.drawable.velocity,
+            Point::new(BASIC_BULLET_SPEED * 0.6, BASIC_BULLET_SPEED * 0.8)
+        );
+
+        let bullet = Bullet::build_aimed_bullet(Point::new(10, 10), Point::new(10, 10));
+
+        assert_eq!(
+            bullet.drawable.velocity,
+            Point::new(0.0, -BASIC_BULLET_SPEED)
+        );
+    }
+}
@@ -1,4 +1,14 @@
-pub const ASTEROID: &str = " ,-,-.
+pub const LARGE_ASTEROID: &str = "  .-~~~-.
+ /  o    \\
+|   .  (  |
+|  __    o|
+ \\ (  .  /
+  `-.__.-'";
+
+pub const MEDIUM_ASTEROID: &str = " ,-,-.
 /.( +.\\
 \\ {. */
  `-`-'";
+
+pub const SMALL_ASTEROID: &str = ",-.
+`-'";
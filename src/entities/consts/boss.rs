@@ -0,0 +1,10 @@
+pub const BOSS_HULL: &str = "  ___/=========\\___
+ /  |           |  \\
+<===|           |===>
+ \\__|           |__/
+    \\__/     \\__/";
+
+pub const BOSS_TURRET: &str = "[V]";
+
+pub const BOSS_CORE: &str = "(@@@)
+(@@@)";
@@ -6,3 +6,7 @@ pub const SPREAD_BULLET: &str = "
      ^
   ^  ^  ^
 ^  ^   ^  ^";
+
+pub const ENEMY_BULLET: &str = "o";
+
+pub const HOMING_MISSILE: &str = "!";
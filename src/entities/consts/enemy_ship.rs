@@ -0,0 +1,2 @@
+pub const ENEMY_SHIP: &str = "/=V=\\
+ \\_/";
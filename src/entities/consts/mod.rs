@@ -1,7 +1,15 @@
 mod asteroid;
+mod boss;
 mod bullet;
+mod enemy_ship;
+mod pickup;
+mod seeker_mine;
 mod spaceship;
 
 pub use asteroid::*;
+pub use boss::*;
 pub use bullet::*;
+pub use enemy_ship::*;
+pub use pickup::*;
+pub use seeker_mine::*;
 pub use spaceship::*;
@@ -0,0 +1,9 @@
+pub const SHIELD_PICKUP: &str = "<S>";
+
+pub const RAPID_FIRE_PICKUP: &str = "<R>";
+
+pub const SPREAD_PICKUP: &str = "<W>";
+
+pub const EXTRA_LIFE_PICKUP: &str = "<+>";
+
+pub const UPGRADE_PICKUP: &str = "<U>";
@@ -0,0 +1 @@
+pub const SEEKER_MINE: &str = "(*)";
@@ -1,28 +1,44 @@
-use crossterm::event::{Event, KeyCode};
-
-pub fn create_event(keycode: KeyCode) -> Event {
-    Event::Key(keycode.into())
-}
+use crate::systems::{Action, InputState};
 
 pub trait Controller {
     fn up(&mut self) -> &mut Self;
     fn down(&mut self) -> &mut Self;
     fn left(&mut self) -> &mut Self;
     fn right(&mut self) -> &mut Self;
+    /// Stops moving in every direction, called before the held directions are applied each loop
+    fn stop(&mut self) -> &mut Self;
+
+    /// Handles one-off actions such as firing, movement is handled through handle_input_state instead
+    fn handle_action(&mut self, action: Action) -> &mut Self;
 
-    fn additional_event_logic(&mut self, event: &Event) -> &mut Self;
+    /// Moves in every direction that is currently held, opposite directions cancel each other out
+    fn handle_input_state(&mut self, input_state: &InputState) {
+        self.stop();
+
+        match (
+            input_state.is_action_held(Action::MoveUp),
+            input_state.is_action_held(Action::MoveDown),
+        ) {
+            (true, false) => {
+                self.up();
+            }
+            (false, true) => {
+                self.down();
+            }
+            _ => {}
+        }
 
-    fn handle_event(&mut self, event: &Event) {
-        if event == &create_event(KeyCode::Up) {
-            self.up();
-        } else if event == &create_event(KeyCode::Down) {
-            self.down();
-        } else if event == &create_event(KeyCode::Left) {
-            self.left();
-        } else if event == &create_event(KeyCode::Right) {
-            self.right();
-        } else {
-            self.additional_event_logic(event);
+        match (
+            input_state.is_action_held(Action::MoveLeft),
+            input_state.is_action_held(Action::MoveRight),
+        ) {
+            (true, false) => {
+                self.left();
+            }
+            (false, true) => {
+                self.right();
+            }
+            _ => {}
         }
     }
 }
@@ -0,0 +1,208 @@
+use crossterm::style::Color;
+
+use crate::{
+    api::display::{Layout, Point},
+    components::{get_updated_health, Drawable, DrawableState, DrawableType, Health},
+};
+
+use super::{consts::ENEMY_SHIP, Bullet};
+
+pub const ENEMY_SHIP_DAMAGE: u32 = 2;
+pub const ENEMY_SHIP_HEALTH: u32 = 2;
+/// Time between shots in milliseconds
+pub const ENEMY_SHIP_FIRE_RATE: u128 = 1500;
+
+/// Speed the ships drift down the screen at while strafing or zig-zagging, in cells per second
+const ENEMY_SHIP_DESCENT_SPEED: f64 = 3.0;
+/// Sideways speed at the middle of a strafe, in cells per second
+const STRAFE_SPEED: f64 = 15.0;
+/// How long a strafe takes to sweep from one side and back, in milliseconds
+const STRAFE_PERIOD: u128 = 4000;
+/// How long a diving ship hovers before it dives, in milliseconds
+const DIVE_DELAY: u128 = 2000;
+/// Speed of a dive in cells per second
+const DIVE_SPEED: f64 = 25.0;
+/// Sideways speed while zig-zagging, in cells per second
+const ZIG_ZAG_SPEED: f64 = 12.0;
+/// How long each leg of a zig-zag lasts, in milliseconds
+const ZIG_ZAG_INTERVAL: u128 = 800;
+
+/// How an enemy ship moves over its lifetime
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementPattern {
+    /// Sweeps smoothly from side to side while drifting down
+    Strafe,
+    /// Drifts down slowly and then dives straight down at speed, it doesn't aim at the player
+    Dive,
+    /// Switches sharply between moving left and right while drifting down
+    ZigZag,
+}
+
+impl MovementPattern {
+    pub const ALL: [MovementPattern; 3] = [
+        MovementPattern::Strafe,
+        MovementPattern::Dive,
+        MovementPattern::ZigZag,
+    ];
+
+    /// Returns the velocity of a ship following the pattern
+    ///
+    /// # Arguments
+    ///
+    /// * `time_alive` - How long the ship has been alive in milliseconds
+    pub fn get_velocity(&self, time_alive: u128) -> Point<f64> {
+        match self {
+            MovementPattern::Strafe => {
+                let phase = (time_alive % STRAFE_PERIOD) as f64 / STRAFE_PERIOD as f64
+                    * std::f64::consts::TAU;
+
+                Point::new(STRAFE_SPEED * phase.cos(), ENEMY_SHIP_DESCENT_SPEED)
+            }
+            MovementPattern::Dive => {
+                if time_alive < DIVE_DELAY {
+                    Point::new(0.0, ENEMY_SHIP_DESCENT_SPEED)
+                } else {
+                    Point::new(0.0, DIVE_SPEED)
+                }
+            }
+            MovementPattern::ZigZag => {
+                let direction = if (time_alive / ZIG_ZAG_INTERVAL).is_multiple_of(2) {
+                    1.0
+                } else {
+                    -1.0
+                };
+
+                Point::new(ZIG_ZAG_SPEED * direction, ENEMY_SHIP_DESCENT_SPEED)
+            }
+        }
+    }
+}
+
+/// An enemy that flies in a pattern and shoots at the player
+pub struct EnemyShip {
+    pub drawable: DrawableState,
+    pub health: u32,
+    pub pattern: MovementPattern,
+    time_alive: u128,
+    fire_time_remaining: u128,
+}
+
+impl EnemyShip {
+    pub fn new(location: Point<i64>, pattern: MovementPattern) -> Self {
+        let map = Layout::from_ascii(ENEMY_SHIP, Color::Red);
+
+        Self {
+            drawable: DrawableState::new(
+                map,
+                location,
+                DrawableType::Enemy(ENEMY_SHIP_DAMAGE),
+                Some(pattern.get_velocity(0)),
+            ),
+            health: ENEMY_SHIP_HEALTH,
+            pattern,
+            time_alive: 0,
+            fire_time_remaining: ENEMY_SHIP_FIRE_RATE,
+        }
+    }
+
+    /// Steers the ship along its pattern and counts down to its next shot
+    ///
+    /// # Returns
+    ///
+    /// * True if the ship is ready to fire this loop
+    pub fn handle_game_loop(&mut self, game_loop_duration: u128) -> bool {
+        self.time_alive += game_loop_duration;
+        self.drawable.velocity = self.pattern.get_velocity(self.time_alive);
+
+        self.fire_time_remaining = self.fire_time_remaining.saturating_sub(game_loop_duration);
+
+        if self.fire_time_remaining > 0 {
+            return false;
+        }
+
+        self.fire_time_remaining = ENEMY_SHIP_FIRE_RATE;
+
+        true
+    }
+
+    /// Builds a bullet fired from the bottom of the ship towards the target world location
+    pub fn fire_at(&self, target: Point<i64>) -> Bullet {
+        let location = self.drawable.get_location();
+        let dimensions = self.drawable.layout.dimensions;
+
+        Bullet::build_enemy_bullet(
+            location + Point::new(dimensions.width / 2, dimensions.height),
+            target,
+        )
+    }
+}
+
+impl Drawable for EnemyShip {
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.set_location(updated_position);
+
+        self
+    }
+
+    fn get_drawable_state(&self) -> &DrawableState {
+        &self.drawable
+    }
+}
+
+impl Health for EnemyShip {
+    fn apply_damage(&mut self, damage: u32) -> &mut Self {
+        self.health = get_updated_health(self.health, damage);
+
+        self
+    }
+
+    fn get_health(&self) -> u32 {
+        self.health
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{api::display::Point, components::DrawableType};
+
+    use super::{
+        EnemyShip, MovementPattern, DIVE_DELAY, DIVE_SPEED, ENEMY_SHIP_FIRE_RATE, ZIG_ZAG_INTERVAL,
+    };
+
+    #[test]
+    fn it_follows_the_movement_patterns() {
+        let strafe = MovementPattern::Strafe;
+
+        // Half way through a strafe the ship is heading back the other way
+        assert!(strafe.get_velocity(0).width > 0.0);
+        assert!(strafe.get_velocity(2000).width < 0.0);
+
+        let dive = MovementPattern::Dive;
+
+        assert!(dive.get_velocity(DIVE_DELAY - 1).height < DIVE_SPEED);
+        assert_eq!(dive.get_velocity(DIVE_DELAY), Point::new(0.0, DIVE_SPEED));
+
+        let zig_zag = MovementPattern::ZigZag;
+
+        assert!(zig_zag.get_velocity(0).width > 0.0);
+        assert!(zig_zag.get_velocity(ZIG_ZAG_INTERVAL).width < 0.0);
+        assert!(zig_zag.get_velocity(ZIG_ZAG_INTERVAL * 2).width > 0.0);
+    }
+
+    #[test]
+    fn it_fires_enemy_ammunition_at_the_fire_rate() {
+        let mut enemy_ship = EnemyShip::new(Point::new(10, 0), MovementPattern::Dive);
+
+        assert!(!enemy_ship.handle_game_loop(ENEMY_SHIP_FIRE_RATE - 1));
+        assert!(enemy_ship.handle_game_loop(1));
+        assert!(!enemy_ship.handle_game_loop(1));
+
+        let bullet = enemy_ship.fire_at(Point::new(12, 20));
+
+        assert!(matches!(
+            bullet.drawable.drawable_type,
+            DrawableType::EnemyAmmunition(..)
+        ));
+        assert!(bullet.drawable.velocity.height > 0.0);
+    }
+}
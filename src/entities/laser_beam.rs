@@ -0,0 +1,209 @@
+use crossterm::style::Color;
+
+use crate::{
+    api::display::{element::DEFAULT_BACKGROUND, Element, Layout, Point},
+    components::{Drawable, DrawableState, DrawableType},
+};
+
+/// Milliseconds between the beam's damage ticks, the beam only takes part in collision detection on these loops
+pub const LASER_BEAM_TICK_INTERVAL: u128 = 100;
+/// Milliseconds between the beam switching elements, this makes the beam flicker while it fires
+const LASER_BEAM_FLICKER_INTERVAL: u128 = 60;
+/// The first row below the top border, beams that don't hit anything stop here
+const LASER_BEAM_TOP_ROW: i64 = 1;
+
+const LASER_BEAM_ELEMENTS: [Element; 2] = [
+    Element::new('|', DEFAULT_BACKGROUND, Color::Red),
+    Element::new('!', DEFAULT_BACKGROUND, Color::Magenta),
+];
+
+/// A continuous beam fired straight up from the ship. It reaches the first enemy above the ship or the top of the world, so its layout is rebuilt with a new length every loop it fires
+pub struct LaserBeam {
+    pub drawable: DrawableState,
+    is_firing: bool,
+    is_damage_tick: bool,
+    tick_elapsed: u128,
+    flicker_elapsed: u128,
+}
+
+impl Default for LaserBeam {
+    fn default() -> Self {
+        Self {
+            drawable: DrawableState::new(
+                Layout::new(&Point::new(1, 1), Some(LASER_BEAM_ELEMENTS[0])),
+                Point::default(),
+                DrawableType::Ammunition(0),
+                None,
+            ),
+            is_firing: false,
+            is_damage_tick: false,
+            tick_elapsed: 0,
+            flicker_elapsed: 0,
+        }
+    }
+}
+
+impl LaserBeam {
+    /// Whether the beam fired this loop and should be drawn
+    pub fn is_firing(&self) -> bool {
+        self.is_firing
+    }
+
+    /// Whether the beam deals its damage this loop, it is left out of collision detection on every other loop
+    pub fn is_damage_tick(&self) -> bool {
+        self.is_firing && self.is_damage_tick
+    }
+
+    /// Fires the beam for this loop, the first damage tick lands as soon as the beam starts
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - World location of the bottom of the beam, the cell above the front of the ship
+    /// * `length` - Number of cells the beam covers upwards from the origin, see get_laser_beam_length
+    /// * `damage` - Damage dealt to whatever the beam touches on each tick
+    pub fn fire(
+        &mut self,
+        origin: Point<i64>,
+        length: i64,
+        damage: u32,
+        game_loop_duration: u128,
+    ) -> &mut Self {
+        if self.is_firing {
+            self.tick_elapsed += game_loop_duration;
+            self.flicker_elapsed += game_loop_duration;
+        } else {
+            self.tick_elapsed = LASER_BEAM_TICK_INTERVAL;
+            self.flicker_elapsed = 0;
+        }
+
+        self.is_firing = true;
+        self.is_damage_tick = self.tick_elapsed >= LASER_BEAM_TICK_INTERVAL;
+        self.tick_elapsed %= LASER_BEAM_TICK_INTERVAL;
+
+        let flicker = (self.flicker_elapsed / LASER_BEAM_FLICKER_INTERVAL) as usize;
+        let element = LASER_BEAM_ELEMENTS[flicker % LASER_BEAM_ELEMENTS.len()];
+        let length = length.max(1);
+
+        self.drawable.layout = Layout::new(&Point::new(1, length), Some(element));
+        self.drawable.drawable_type = DrawableType::Ammunition(damage);
+
+        // The beam appears at full length rather than travelling, so it has no path to sweep
+        self.set_position(origin.sub_height(length - 1).into())
+    }
+
+    /// Switches the beam off, it starts with a damage tick the next time it fires
+    pub fn stop(&mut self) -> &mut Self {
+        self.is_firing = false;
+        self.is_damage_tick = false;
+
+        self
+    }
+}
+
+impl Drawable for LaserBeam {
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.teleport(updated_position);
+
+        self
+    }
+
+    fn get_drawable_state(&self) -> &DrawableState {
+        &self.drawable
+    }
+}
+
+/// Returns how many cells a beam fired straight up from the origin covers. The beam ends on the lowest obstacle cell in its column so that cell is hit, beams that miss everything stop below the top border
+///
+/// # Arguments
+///
+/// * `origin` - World location of the bottom of the beam
+/// * `obstacles` - Drawables that block the beam
+pub fn get_laser_beam_length(origin: Point<i64>, obstacles: &[&DrawableState]) -> i64 {
+    let end_row = obstacles
+        .iter()
+        .filter_map(|obstacle| {
+            let location = obstacle.get_location();
+            let column = origin.width - location.width;
+
+            if column < 0 || column >= obstacle.layout.dimensions.width {
+                return None;
+            }
+
+            obstacle
+                .layout
+                .map
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|(_, row)| row.get(column as usize).is_some_and(|cell| cell.is_some()))
+                .map(|(row, _)| location.height + row as i64)
+                .find(|row| *row <= origin.height)
+        })
+        .max()
+        .unwrap_or(LASER_BEAM_TOP_ROW);
+
+    origin.height - end_row + 1
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::{
+        api::display::{Layout, Point},
+        components::{DrawableState, DrawableType},
+    };
+
+    use super::{get_laser_beam_length, LaserBeam, LASER_BEAM_TICK_INTERVAL};
+
+    #[test]
+    fn it_stops_at_the_nearest_obstacle_in_its_column() {
+        let origin = Point::new(10, 20);
+        let obstacle = |ascii: &str, location: Point<i64>| {
+            DrawableState::new(
+                Layout::from_ascii(ascii, Color::White),
+                location,
+                DrawableType::Enemy(1),
+                None,
+            )
+        };
+
+        // Only the cells in the beam's column block it, and obstacles below the origin are ignored
+        let far = obstacle("###", Point::new(9, 5));
+        let near = obstacle("#\n #", Point::new(9, 10));
+        let beside = obstacle("#", Point::new(11, 15));
+        let below = obstacle("#", Point::new(10, 21));
+
+        assert_eq!(
+            get_laser_beam_length(origin, &[&far, &near, &beside, &below]),
+            10
+        );
+        assert_eq!(get_laser_beam_length(origin, &[&far, &beside]), 16);
+        assert_eq!(get_laser_beam_length(origin, &[]), 20);
+    }
+
+    #[test]
+    fn it_deals_damage_on_ticks() {
+        let mut beam = LaserBeam::default();
+
+        beam.fire(Point::new(10, 20), 5, 2, 0);
+
+        assert!(beam.is_damage_tick());
+        assert_eq!(beam.drawable.get_location(), Point::new(10, 16));
+        assert_eq!(beam.drawable.layout.dimensions, Point::new(1, 5));
+        assert_eq!(beam.drawable.drawable_type, DrawableType::Ammunition(2));
+
+        beam.fire(Point::new(10, 20), 8, 2, LASER_BEAM_TICK_INTERVAL - 1);
+
+        assert!(!beam.is_damage_tick());
+
+        beam.fire(Point::new(10, 20), 8, 2, 1);
+
+        assert!(beam.is_damage_tick());
+
+        beam.stop();
+
+        assert!(!beam.is_firing());
+        assert!(!beam.is_damage_tick());
+    }
+}
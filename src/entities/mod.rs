@@ -1,12 +1,26 @@
 mod asteroid;
+mod asteroid_shape;
 mod borders;
+mod boss;
 mod bullet;
 mod consts;
 pub mod controller;
+mod enemy_ship;
+mod laser_beam;
+mod pickup;
 pub mod player;
+mod seeker_mine;
+mod weapon;
 
 pub use asteroid::*;
+pub use asteroid_shape::*;
 pub use borders::Borders;
+pub use boss::*;
 pub use bullet::*;
 pub use controller::Controller;
+pub use enemy_ship::*;
+pub use laser_beam::*;
+pub use pickup::*;
 pub use player::Player;
+pub use seeker_mine::*;
+pub use weapon::*;
@@ -0,0 +1,110 @@
+use crossterm::style::Color;
+
+use crate::{
+    api::display::{Layout, Point},
+    components::{get_updated_health, Drawable, DrawableState, DrawableType, Health},
+};
+
+use super::consts::{
+    EXTRA_LIFE_PICKUP, RAPID_FIRE_PICKUP, SHIELD_PICKUP, SPREAD_PICKUP, UPGRADE_PICKUP,
+};
+
+/// Speed pickups drift down the world in cells per second
+const PICKUP_FALL_SPEED: f64 = 5.0;
+
+/// What the player gets from collecting a pickup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickupKind {
+    /// Absorbs every hit for a while
+    Shield,
+    /// Halves the cooldown of every weapon for a while
+    RapidFire,
+    /// Every basic weapon shot also fires a spread shot, this lasts for the rest of the game
+    Spread,
+    /// One more life
+    ExtraLife,
+    /// Raises the level of the selected weapon and refills its ammo
+    Upgrade,
+}
+
+impl PickupKind {
+    pub const ALL: [PickupKind; 5] = [
+        PickupKind::Shield,
+        PickupKind::RapidFire,
+        PickupKind::Spread,
+        PickupKind::ExtraLife,
+        PickupKind::Upgrade,
+    ];
+
+    /// The name shown in the HUD while the effect is active
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            PickupKind::Shield => "SHIELD",
+            PickupKind::RapidFire => "RAPID FIRE",
+            PickupKind::Spread => "SPREAD",
+            PickupKind::ExtraLife => "EXTRA LIFE",
+            PickupKind::Upgrade => "UPGRADE",
+        }
+    }
+
+    fn get_sprite(&self) -> (&'static str, Color) {
+        match self {
+            PickupKind::Shield => (SHIELD_PICKUP, Color::Blue),
+            PickupKind::RapidFire => (RAPID_FIRE_PICKUP, Color::Yellow),
+            PickupKind::Spread => (SPREAD_PICKUP, Color::Green),
+            PickupKind::ExtraLife => (EXTRA_LIFE_PICKUP, Color::Red),
+            PickupKind::Upgrade => (UPGRADE_PICKUP, Color::Magenta),
+        }
+    }
+}
+
+/// A collectible dropped by destroyed enemies, it drifts down the world until the player flies into it
+pub struct Pickup {
+    pub drawable: DrawableState,
+    pub health: u32,
+    pub kind: PickupKind,
+}
+
+impl Pickup {
+    /// Creates a pickup centered on the location
+    pub fn new(kind: PickupKind, center: Point<f64>) -> Self {
+        let (sprite, color) = kind.get_sprite();
+        let layout = Layout::from_ascii(sprite, color);
+        let location = center.round().sub_width(layout.dimensions.width / 2);
+
+        Self {
+            drawable: DrawableState::new(
+                layout,
+                location,
+                DrawableType::Pickup,
+                Some(Point::new(0.0, PICKUP_FALL_SPEED)),
+            ),
+            health: 1,
+            kind,
+        }
+    }
+}
+
+impl Drawable for Pickup {
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.set_location(updated_position);
+
+        self
+    }
+
+    fn get_drawable_state(&self) -> &DrawableState {
+        &self.drawable
+    }
+}
+
+impl Health for Pickup {
+    fn apply_damage(&mut self, damage: u32) -> &mut Self {
+        self.health = get_updated_health(self.health, damage);
+
+        self
+    }
+
+    fn get_health(&self) -> u32 {
+        self.health
+    }
+}
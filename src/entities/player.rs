@@ -1,25 +1,52 @@
-use crossterm::{event::KeyCode, style::Color};
+use crossterm::style::Color;
 
 use crate::{
     api::display::{Layout, Point},
-    components::{get_updated_health, Drawable, DrawableState, DrawableType, Health, Spawnable},
-    systems::EntityController,
+    components::{
+        get_nearest_target, get_updated_health, CollisionLayer, CollisionMask, Drawable,
+        DrawableState, DrawableType, Health, SteeringTarget, PADDING_OFFSET,
+    },
+    systems::{Action, EntityController, ShipPhysics, ShipPhysicsConfig},
 };
 
-use super::{consts::SPACE_SHIP, controller::create_event, Bullet, Controller};
+use super::{consts::SPACE_SHIP, Bullet, Controller, LaserBeam, PickupKind, Weapon, WeaponKind};
 
 pub struct Player {
     pub drawable: DrawableState,
     pub health: u32,
     pub bullet_entity_controller: EntityController<Bullet>,
+    /// Movement is driven by thrust, the drawable's location and velocity are updated from this each loop
+    pub physics: ShipPhysics,
+    /// How long the player ignores collisions after being hit, in milliseconds
+    pub invulnerability_duration: u128,
+    /// When true, losing a life removes the ship for the respawn delay and then places it back at a safe spawn point
+    pub respawn_on_hit: bool,
+    /// One of each kind of weapon, in the order of WeaponKind::ALL
+    pub weapons: Vec<Weapon>,
+    /// The weapon fired by the fire action and mouse clicks
+    pub selected_weapon: WeaponKind,
+    /// Spread shots are fired alongside every basic weapon shot once a spread pickup has been collected
+    pub has_spread: bool,
+    /// Fired while the laser is selected and the fire action is held
+    pub laser_beam: LaserBeam,
+    invulnerability_time_remaining: u128,
+    respawn_time_remaining: u128,
+    shield_time_remaining: u128,
+    rapid_fire_time_remaining: u128,
+    // Dimensions of the world the player is kept inside, see set_bounds
+    bounds: Option<Point<i64>>,
 }
 
-const WIDTH_MAX_VELOCITY: i64 = 33;
-const HEIGHT_MAX_VELOCITY: i64 = 20;
-
-trait CanSpawn {
-    fn get_spawnable_entities<T>(&self) -> Spawnable<T>;
-}
+pub const INVULNERABILITY_DURATION: u128 = 2000;
+pub const RESPAWN_DELAY: u128 = 1000;
+/// The HUD can only show single digit lives, so extra lives stop at this
+pub const MAX_PLAYER_HEALTH: u32 = 9;
+/// How long a shield pickup absorbs hits for in milliseconds
+pub const SHIELD_DURATION: u128 = 10000;
+/// How long a rapid fire pickup lasts for in milliseconds
+pub const RAPID_FIRE_DURATION: u128 = 8000;
+/// Weapon cooldowns are multiplied by this during rapid fire
+pub const RAPID_FIRE_COOLDOWN_SCALE: f64 = 0.5;
 
 impl Player {
     pub fn new(mut default_position: Option<Point<i64>>) -> Self {
@@ -32,6 +59,11 @@ impl Player {
 
         let layout = Layout::from_ascii(SPACE_SHIP, Color::Cyan);
 
+        let physics = ShipPhysics::new(
+            ShipPhysicsConfig::default(),
+            default_position.unwrap().into(),
+        );
+
         Self {
             drawable: DrawableState::new(
                 layout,
@@ -39,14 +71,254 @@ impl Player {
                 DrawableType::Player,
                 None,
             ),
-            health: 9,
+            health: MAX_PLAYER_HEALTH,
             bullet_entity_controller: EntityController::new(),
+            physics,
+            invulnerability_duration: INVULNERABILITY_DURATION,
+            respawn_on_hit: false,
+            weapons: WeaponKind::ALL.into_iter().map(Weapon::new).collect(),
+            selected_weapon: WeaponKind::Basic,
+            has_spread: false,
+            laser_beam: LaserBeam::default(),
+            invulnerability_time_remaining: 0,
+            respawn_time_remaining: 0,
+            shield_time_remaining: 0,
+            rapid_fire_time_remaining: 0,
+            bounds: None,
         }
     }
 
+    /// Sets the dimensions of the world the player is kept inside, this has to be set before the player is moved
+    pub fn set_bounds(&mut self, dimensions: Point<i64>) -> &mut Self {
+        self.bounds = Some(dimensions);
+
+        self
+    }
+
+    /// Whether the player is currently ignoring enemies after being hit, pickups can still be collected
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerability_time_remaining > 0
+    }
+
+    /// Whether the player has been removed from play while waiting to respawn
+    pub fn is_respawning(&self) -> bool {
+        self.respawn_time_remaining > 0
+    }
+
+    /// Whether a shield pickup is absorbing hits
+    pub fn is_shielded(&self) -> bool {
+        self.shield_time_remaining > 0
+    }
+
+    /// Whether a rapid fire pickup is shortening the weapon cooldowns
+    pub fn is_rapid_fire(&self) -> bool {
+        self.rapid_fire_time_remaining > 0
+    }
+
+    /// Grants the pickup's effect, collecting a timed effect that is already active restarts its timer
+    pub fn apply_pickup(&mut self, kind: PickupKind) -> &mut Self {
+        match kind {
+            PickupKind::Shield => self.shield_time_remaining = SHIELD_DURATION,
+            PickupKind::RapidFire => self.rapid_fire_time_remaining = RAPID_FIRE_DURATION,
+            PickupKind::Spread => self.has_spread = true,
+            PickupKind::ExtraLife => self.health = (self.health + 1).min(MAX_PLAYER_HEALTH),
+            PickupKind::Upgrade => {
+                self.get_weapon_mut(self.selected_weapon).upgrade();
+            }
+        }
+
+        self
+    }
+
+    /// Returns the active pickup effects along with their remaining time in milliseconds, None for effects that don't run out
+    pub fn get_active_power_ups(&self) -> Vec<(PickupKind, Option<u128>)> {
+        let mut power_ups = vec![];
+
+        if self.is_shielded() {
+            power_ups.push((PickupKind::Shield, Some(self.shield_time_remaining)));
+        }
+
+        if self.is_rapid_fire() {
+            power_ups.push((PickupKind::RapidFire, Some(self.rapid_fire_time_remaining)));
+        }
+
+        if self.has_spread {
+            power_ups.push((PickupKind::Spread, None));
+        }
+
+        power_ups
+    }
+
+    /// Called after the player takes damage, starts the respawn delay if respawning is enabled, otherwise the invulnerability window starts straight away
+    pub fn handle_hit(&mut self) -> &mut Self {
+        self.drawable.velocity = Point::default();
+        self.physics.stop().clear_thrust();
+
+        if self.respawn_on_hit {
+            self.respawn_time_remaining = RESPAWN_DELAY;
+        } else {
+            self.invulnerability_time_remaining = self.invulnerability_duration;
+        }
+
+        self.update_collision_mask()
+    }
+
+    /// Only pickups collide with the player while invulnerable, otherwise the player collides with everything its type does
+    fn update_collision_mask(&mut self) -> &mut Self {
+        self.drawable.collision_mask = if self.is_invulnerable() {
+            CollisionMask::from_layers(&[CollisionLayer::Pickup])
+        } else {
+            CollisionLayer::get_defaults(DrawableType::Player).1
+        };
+
+        self
+    }
+
+    /// Places the player at the provided location and starts the invulnerability window
+    pub fn respawn(&mut self, location: Point<i64>) -> &mut Self {
+        self.respawn_time_remaining = 0;
+        self.invulnerability_time_remaining = self.invulnerability_duration;
+
+        self.update_collision_mask().set_position(location.into())
+    }
+
+    /// Advances the invulnerability, respawn and pickup timers
+    ///
+    /// # Returns
+    ///
+    /// * True if the respawn delay finished this loop, the caller is then responsible for calling respawn with a safe location
+    pub fn handle_game_loop(&mut self, game_loop_duration: u128) -> bool {
+        self.invulnerability_time_remaining = self
+            .invulnerability_time_remaining
+            .saturating_sub(game_loop_duration);
+        self.shield_time_remaining = self
+            .shield_time_remaining
+            .saturating_sub(game_loop_duration);
+        self.rapid_fire_time_remaining = self
+            .rapid_fire_time_remaining
+            .saturating_sub(game_loop_duration);
+
+        for weapon in self.weapons.iter_mut() {
+            weapon.handle_game_loop(game_loop_duration);
+        }
+
+        self.update_collision_mask();
+
+        if !self.is_respawning() {
+            return false;
+        }
+
+        self.respawn_time_remaining = self
+            .respawn_time_remaining
+            .saturating_sub(game_loop_duration);
+
+        !self.is_respawning()
+    }
+
+    /// Fires the selected weapon from the front of the ship towards the target world location
+    pub fn fire_at(&mut self, target: Point<i64>) -> &mut Self {
+        self.fire_weapon(self.selected_weapon, Some(target))
+    }
+
+    fn get_weapon_mut(&mut self, kind: WeaponKind) -> &mut Weapon {
+        self.weapons
+            .iter_mut()
+            .find(|weapon| weapon.kind == kind)
+            .expect("The player has every kind of weapon")
+    }
+
+    /// Fires the weapon if its cooldown has finished and it has the ammo or heat to fire, nothing happens otherwise
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The weapon to fire
+    /// * `target` - World location to aim at, None fires straight up
+    fn fire_weapon(&mut self, kind: WeaponKind, target: Option<Point<i64>>) -> &mut Self {
+        let origin = self.get_center_of_player();
+        let cooldown_scale = if self.is_rapid_fire() {
+            RAPID_FIRE_COOLDOWN_SCALE
+        } else {
+            1.0
+        };
+
+        let mut bullets = self
+            .get_weapon_mut(kind)
+            .fire(origin, target, cooldown_scale);
+
+        if self.has_spread && kind == WeaponKind::Basic && !bullets.is_empty() {
+            bullets.push(Bullet::build_spread_bullet(origin.sub_width(4)));
+        }
+
+        for bullet in bullets {
+            self.bullet_entity_controller.spawn_entity(bullet);
+        }
+
+        self
+    }
+
+    /// Fires the laser beam for this loop while the laser is selected and has energy, the beam is switched off otherwise
+    ///
+    /// # Arguments
+    ///
+    /// * `is_fire_held` - Whether the fire action is held
+    /// * `length` - Number of cells the beam reaches up from get_laser_beam_origin
+    pub fn handle_laser_beam(
+        &mut self,
+        is_fire_held: bool,
+        length: i64,
+        game_loop_duration: u128,
+    ) -> &mut Self {
+        let is_firing = is_fire_held
+            && self.selected_weapon == WeaponKind::Laser
+            && !self.is_respawning()
+            && self
+                .get_weapon_mut(WeaponKind::Laser)
+                .drain_energy(game_loop_duration);
+
+        if !is_firing {
+            self.laser_beam.stop();
+
+            return self;
+        }
+
+        // The beam hits harder as the laser levels up
+        let damage = self.get_weapon_mut(WeaponKind::Laser).level;
+        let origin = self.get_laser_beam_origin();
+
+        self.laser_beam
+            .fire(origin, length, damage, game_loop_duration);
+
+        self
+    }
+
+    /// Returns the world location of the bottom of the laser beam, the cell above the front of the ship
+    pub fn get_laser_beam_origin(&self) -> Point<i64> {
+        self.get_center_of_player().sub_height(1)
+    }
+
+    /// Turns the player's homing missiles towards the nearest target, missiles fly straight when there are no targets
+    pub fn steer_missiles(
+        &mut self,
+        targets: &[SteeringTarget],
+        game_loop_duration: u128,
+    ) -> &mut Self {
+        for bullet in self.bullet_entity_controller.get_entities_mut() {
+            let Some(steering) = bullet.steering else {
+                continue;
+            };
+
+            if let Some(target) = get_nearest_target(bullet.drawable.get_center(), targets) {
+                bullet.drawable.velocity =
+                    steering.get_steered_velocity(&bullet.drawable, &target, game_loop_duration);
+            }
+        }
+
+        self
+    }
+
     fn get_center_of_player(&self) -> Point<i64> {
         self.drawable
-            .location
+            .get_location()
             .add_width(self.drawable.layout.dimensions.width / 2)
     }
 }
@@ -56,8 +328,30 @@ impl Drawable for Player {
         &self.drawable
     }
 
-    fn set_position(&mut self, updated_position: Point<i64>) -> &mut Self {
-        self.drawable.location = updated_position;
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        // Setting the position directly is only done when placing the ship, so it has no path to sweep for collisions
+        self.drawable.teleport(updated_position);
+        self.physics.set_location(updated_position);
+
+        self
+    }
+
+    /// Moves the player using the ship physics, the player is kept inside its bounds
+    fn update_position(&mut self, game_loop_duration: u128) -> &mut Self {
+        let dimensions = self
+            .bounds
+            .expect("The player's bounds must be set before it moves");
+
+        let min_location = Point::new(PADDING_OFFSET, PADDING_OFFSET);
+        let max_location = dimensions
+            - self.drawable.layout.dimensions
+            - Point::new(PADDING_OFFSET, PADDING_OFFSET);
+
+        self.physics
+            .handle_game_loop(game_loop_duration, min_location, max_location);
+
+        self.drawable.set_location(self.physics.get_location());
+        self.drawable.velocity = self.physics.get_velocity();
 
         self
     }
@@ -65,40 +359,53 @@ impl Drawable for Player {
 
 impl Controller for Player {
     fn up(&mut self) -> &mut Self {
-        self.drawable.velocity = Point::new(0, -HEIGHT_MAX_VELOCITY);
+        self.physics.set_thrust_height(-1.0);
 
         self
     }
 
     fn down(&mut self) -> &mut Self {
-        self.drawable.velocity = Point::new(0, HEIGHT_MAX_VELOCITY);
+        self.physics.set_thrust_height(1.0);
 
         self
     }
 
     fn left(&mut self) -> &mut Self {
-        self.drawable.velocity = Point::new(-WIDTH_MAX_VELOCITY, 0);
+        self.physics.set_thrust_width(-1.0);
 
         self
     }
 
     fn right(&mut self) -> &mut Self {
-        self.drawable.velocity = Point::new(WIDTH_MAX_VELOCITY, 0);
+        self.physics.set_thrust_width(1.0);
+
+        self
+    }
+
+    /// Stops thrusting, the ship keeps drifting until drag slows it down
+    fn stop(&mut self) -> &mut Self {
+        self.physics.clear_thrust();
 
         self
     }
 
-    fn additional_event_logic(&mut self, event: &crossterm::event::Event) -> &mut Self {
-        if event == &create_event(KeyCode::Char(' ')) {
-            self.bullet_entity_controller
-                .spawn_entity(Bullet::build_basic_bullet(
-                    self.get_center_of_player().add_height(1),
-                ));
-        } else if event == &create_event(KeyCode::Enter) {
-            self.bullet_entity_controller
-                .spawn_entity(Bullet::build_spread_bullet(
-                    self.get_center_of_player().sub_width(4),
-                ));
+    fn handle_action(&mut self, action: Action) -> &mut Self {
+        match action {
+            Action::Fire => {
+                self.fire_weapon(self.selected_weapon, None);
+            }
+            // Spread shots and missiles can be fired without switching weapons
+            Action::FireMissile => {
+                self.fire_weapon(WeaponKind::Missiles, None);
+            }
+            Action::FireSpread => {
+                self.fire_weapon(WeaponKind::Spread, None);
+            }
+            Action::SelectBasicWeapon => self.selected_weapon = WeaponKind::Basic,
+            Action::SelectSpreadWeapon => self.selected_weapon = WeaponKind::Spread,
+            Action::SelectLaser => self.selected_weapon = WeaponKind::Laser,
+            Action::SelectMissiles => self.selected_weapon = WeaponKind::Missiles,
+            _ => {}
         }
 
         self
@@ -116,3 +423,167 @@ impl Health for Player {
         self.health
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::api::display::Point;
+
+    use crate::{
+        components::CollisionLayer,
+        entities::{PickupKind, MAX_WEAPON_ENERGY},
+        systems::{Action, EntityController},
+    };
+
+    use super::{
+        Controller, Player, WeaponKind, INVULNERABILITY_DURATION, RAPID_FIRE_DURATION,
+        RESPAWN_DELAY,
+    };
+
+    #[test]
+    fn it_is_invulnerable_for_the_duration_after_a_hit() {
+        let mut player = Player::new(None);
+
+        player.handle_hit();
+
+        assert!(player.is_invulnerable());
+        assert!(!player.is_respawning());
+
+        // Pickups can still be collected while enemies are ignored
+        let collision_mask = player.drawable.collision_mask;
+
+        assert!(collision_mask.contains(CollisionLayer::Pickup));
+        assert!(!collision_mask.contains(CollisionLayer::Enemy));
+        assert!(!collision_mask.contains(CollisionLayer::EnemyAmmunition));
+
+        player.handle_game_loop(INVULNERABILITY_DURATION - 1);
+
+        assert!(player.is_invulnerable());
+
+        player.handle_game_loop(1);
+
+        assert!(!player.is_invulnerable());
+        assert!(player
+            .drawable
+            .collision_mask
+            .contains(CollisionLayer::Enemy));
+    }
+
+    #[test]
+    fn it_waits_for_the_respawn_delay_before_respawning() {
+        let mut player = Player::new(None);
+        player.respawn_on_hit = true;
+
+        player.handle_hit();
+
+        assert!(player.is_respawning());
+        assert!(!player.is_invulnerable());
+
+        assert!(!player.handle_game_loop(RESPAWN_DELAY - 1));
+        assert!(player.handle_game_loop(1));
+
+        player.respawn(Point::new(10, 10));
+
+        assert_eq!(player.drawable.get_location(), Point::new(10, 10));
+        assert!(player.is_invulnerable());
+    }
+
+    fn fire(player: &mut Player) -> usize {
+        player.bullet_entity_controller = EntityController::new();
+        player.handle_action(Action::Fire);
+
+        player
+            .bullet_entity_controller
+            .get_all_drawable_states()
+            .len()
+    }
+
+    #[test]
+    fn it_applies_pickup_effects() {
+        let mut player = Player::new(None);
+
+        assert_eq!(fire(&mut player), 1);
+
+        // The basic weapon is still cooling down
+        player.handle_game_loop(100);
+
+        assert_eq!(fire(&mut player), 0);
+
+        player.handle_game_loop(1000);
+        player.apply_pickup(PickupKind::RapidFire);
+
+        assert_eq!(fire(&mut player), 1);
+
+        player.handle_game_loop(100);
+
+        assert_eq!(fire(&mut player), 1);
+
+        player.apply_pickup(PickupKind::Spread);
+        player.handle_game_loop(100);
+
+        assert_eq!(fire(&mut player), 2);
+        assert_eq!(
+            player.get_active_power_ups(),
+            vec![
+                (PickupKind::RapidFire, Some(RAPID_FIRE_DURATION - 200)),
+                (PickupKind::Spread, None)
+            ]
+        );
+
+        // Lives can't go above what the HUD can show
+        let health = player.health;
+
+        player.apply_pickup(PickupKind::ExtraLife);
+
+        assert_eq!(player.health, health);
+
+        player.health = 3;
+        player.apply_pickup(PickupKind::ExtraLife);
+
+        assert_eq!(player.health, 4);
+    }
+
+    #[test]
+    fn it_fires_and_upgrades_the_selected_weapon() {
+        let mut player = Player::new(None);
+
+        player.handle_action(Action::SelectMissiles);
+        player.apply_pickup(PickupKind::Upgrade);
+
+        // Only the selected weapon is upgraded
+        assert_eq!(player.get_weapon_mut(WeaponKind::Missiles).level, 2);
+        assert_eq!(player.get_weapon_mut(WeaponKind::Basic).level, 1);
+        assert_eq!(fire(&mut player), 2);
+        assert!(player
+            .bullet_entity_controller
+            .get_entities()
+            .all(|bullet| bullet.steering.is_some()));
+    }
+
+    #[test]
+    fn it_fires_the_laser_beam_while_fire_is_held() {
+        let mut player = Player::new(Some(Point::new(10, 20)));
+
+        // The beam only fires with the laser selected
+        player.handle_laser_beam(true, 5, 100);
+
+        assert!(!player.laser_beam.is_firing());
+
+        player.handle_action(Action::SelectLaser);
+
+        // Pressing fire with the laser selected doesn't fire projectiles
+        assert_eq!(fire(&mut player), 0);
+
+        player.handle_laser_beam(true, 5, 100);
+
+        assert!(player.laser_beam.is_firing());
+        assert_eq!(
+            player.laser_beam.drawable.get_location(),
+            player.get_laser_beam_origin().sub_height(4)
+        );
+        assert!(player.weapons[2].energy < MAX_WEAPON_ENERGY);
+
+        player.handle_laser_beam(false, 5, 100);
+
+        assert!(!player.laser_beam.is_firing());
+    }
+}
@@ -0,0 +1,80 @@
+use crossterm::style::Color;
+
+use crate::{
+    api::display::{Layout, Point},
+    components::{
+        get_updated_health, Drawable, DrawableState, DrawableType, Health, Steering,
+        SteeringBehaviour, SteeringTarget,
+    },
+};
+
+use super::consts::SEEKER_MINE;
+
+pub const SEEKER_MINE_DAMAGE: u32 = 2;
+pub const SEEKER_MINE_HEALTH: u32 = 1;
+
+/// Speed of a seeker mine in cells per second, slow enough to outrun
+const SEEKER_MINE_SPEED: f64 = 6.0;
+/// How quickly a seeker mine turns in radians per second
+const SEEKER_MINE_TURN_RATE: f64 = 1.5;
+
+/// A slow enemy that drifts towards the player, it can be shot down
+pub struct SeekerMine {
+    pub drawable: DrawableState,
+    pub health: u32,
+    pub steering: Steering,
+}
+
+impl SeekerMine {
+    pub fn new(location: Point<i64>) -> Self {
+        let map = Layout::from_ascii(SEEKER_MINE, Color::Yellow);
+
+        Self {
+            drawable: DrawableState::new(
+                map,
+                location,
+                DrawableType::Enemy(SEEKER_MINE_DAMAGE),
+                Some(Point::new(0.0, SEEKER_MINE_SPEED)),
+            ),
+            health: SEEKER_MINE_HEALTH,
+            steering: Steering::new(
+                SteeringBehaviour::Seek,
+                SEEKER_MINE_SPEED,
+                SEEKER_MINE_TURN_RATE,
+            ),
+        }
+    }
+
+    /// Turns the mine towards the target, or away from it while fleeing
+    pub fn steer(&mut self, target: &SteeringTarget, game_loop_duration: u128) -> &mut Self {
+        self.drawable.velocity =
+            self.steering
+                .get_steered_velocity(&self.drawable, target, game_loop_duration);
+
+        self
+    }
+}
+
+impl Drawable for SeekerMine {
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.set_location(updated_position);
+
+        self
+    }
+
+    fn get_drawable_state(&self) -> &DrawableState {
+        &self.drawable
+    }
+}
+
+impl Health for SeekerMine {
+    fn apply_damage(&mut self, damage: u32) -> &mut Self {
+        self.health = get_updated_health(self.health, damage);
+
+        self
+    }
+
+    fn get_health(&self) -> u32 {
+        self.health
+    }
+}
@@ -0,0 +1,401 @@
+use crate::api::display::Point;
+
+use super::Bullet;
+
+/// Weapons can be upgraded up to this level, each level changes the weapon's projectile pattern
+pub const MAX_WEAPON_LEVEL: u32 = 3;
+
+/// Sideways speed of the angled bullets in the higher level patterns, in cells per second
+const ANGLED_BULLET_SPEED: f64 = 6.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeaponKind {
+    Basic,
+    Spread,
+    Laser,
+    Missiles,
+}
+
+impl WeaponKind {
+    pub const ALL: [WeaponKind; 4] = [
+        WeaponKind::Basic,
+        WeaponKind::Spread,
+        WeaponKind::Laser,
+        WeaponKind::Missiles,
+    ];
+
+    /// The name shown in the HUD
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            WeaponKind::Basic => "BASIC",
+            WeaponKind::Spread => "SPREAD",
+            WeaponKind::Laser => "LASER",
+            WeaponKind::Missiles => "MISSILES",
+        }
+    }
+
+    /// Milliseconds between shots
+    fn get_cooldown(&self) -> u128 {
+        match self {
+            WeaponKind::Basic => 150,
+            WeaponKind::Spread => 500,
+            // The laser fires continuously while its energy lasts
+            WeaponKind::Laser => 0,
+            WeaponKind::Missiles => 600,
+        }
+    }
+
+    fn get_limit(&self) -> WeaponLimit {
+        match self {
+            WeaponKind::Basic => WeaponLimit::None,
+            WeaponKind::Spread => WeaponLimit::Heat {
+                per_shot: 20.0,
+                cooling_rate: 25.0,
+            },
+            WeaponKind::Laser => WeaponLimit::Energy {
+                drain_rate: 40.0,
+                recharge_rate: 15.0,
+            },
+            WeaponKind::Missiles => WeaponLimit::Ammo(8),
+        }
+    }
+}
+
+/// What stops a weapon being fired as fast as its cooldown allows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeaponLimit {
+    None,
+    /// The most ammo the weapon holds, each shot uses one
+    Ammo(u32),
+    /// Each shot adds heat out of 100 and the weapon cools by the rate every second. The weapon overheats at 100 and can't fire until it has cooled completely
+    Heat {
+        per_shot: f64,
+        cooling_rate: f64,
+    },
+    /// The weapon fires a continuous beam rather than projectiles. Firing drains energy out of 100 by the drain rate every second and the weapon always recharges by the recharge rate every second. Once empty it can't fire until it has recharged completely
+    Energy {
+        drain_rate: f64,
+        recharge_rate: f64,
+    },
+}
+
+/// Heat at which a heat limited weapon overheats
+pub const MAX_WEAPON_HEAT: f64 = 100.0;
+/// Energy an energy limited weapon starts with and recharges to
+pub const MAX_WEAPON_ENERGY: f64 = 100.0;
+
+pub struct Weapon {
+    pub kind: WeaponKind,
+    /// From 1 to MAX_WEAPON_LEVEL
+    pub level: u32,
+    pub limit: WeaponLimit,
+    pub ammo: u32,
+    pub heat: f64,
+    pub energy: f64,
+    locked_out: bool,
+    cooldown_remaining: u128,
+}
+
+impl Weapon {
+    /// Creates a level 1 weapon with full ammo
+    pub fn new(kind: WeaponKind) -> Self {
+        let limit = kind.get_limit();
+
+        Self {
+            kind,
+            level: 1,
+            limit,
+            ammo: match limit {
+                WeaponLimit::Ammo(max_ammo) => max_ammo,
+                _ => 0,
+            },
+            heat: 0.0,
+            energy: MAX_WEAPON_ENERGY,
+            locked_out: false,
+            cooldown_remaining: 0,
+        }
+    }
+
+    /// Whether the weapon overheated or ran out of energy and is waiting to recover completely
+    pub fn is_locked_out(&self) -> bool {
+        self.locked_out
+    }
+
+    /// Whether the weapon's cooldown has finished and it has the ammo, heat or energy to fire
+    pub fn can_fire(&self) -> bool {
+        self.cooldown_remaining == 0
+            && !self.locked_out
+            && (!matches!(self.limit, WeaponLimit::Ammo(_)) || self.ammo > 0)
+    }
+
+    /// Raises the weapon's level and refills its ammo
+    ///
+    /// # Returns
+    ///
+    /// False if the weapon was already at the max level, the ammo is still refilled
+    pub fn upgrade(&mut self) -> bool {
+        if let WeaponLimit::Ammo(max_ammo) = self.limit {
+            self.ammo = max_ammo;
+        }
+
+        if self.level >= MAX_WEAPON_LEVEL {
+            return false;
+        }
+
+        self.level += 1;
+
+        true
+    }
+
+    /// Advances the cooldown, cools heat limited weapons and recharges energy limited weapons
+    pub fn handle_game_loop(&mut self, game_loop_duration: u128) -> &mut Self {
+        self.cooldown_remaining = self.cooldown_remaining.saturating_sub(game_loop_duration);
+
+        let seconds = game_loop_duration as f64 / 1000.0;
+
+        match self.limit {
+            WeaponLimit::Heat { cooling_rate, .. } => {
+                self.heat = (self.heat - cooling_rate * seconds).max(0.0);
+
+                if self.heat == 0.0 {
+                    self.locked_out = false;
+                }
+            }
+            WeaponLimit::Energy { recharge_rate, .. } => {
+                self.energy = (self.energy + recharge_rate * seconds).min(MAX_WEAPON_ENERGY);
+
+                if self.energy == MAX_WEAPON_ENERGY {
+                    self.locked_out = false;
+                }
+            }
+            WeaponLimit::None | WeaponLimit::Ammo(_) => {}
+        }
+
+        self
+    }
+
+    /// Drains energy for a loop of continuous fire
+    ///
+    /// # Returns
+    ///
+    /// Whether the weapon fired this loop, false for weapons that aren't energy limited or are waiting to recharge
+    pub fn drain_energy(&mut self, game_loop_duration: u128) -> bool {
+        let WeaponLimit::Energy { drain_rate, .. } = self.limit else {
+            return false;
+        };
+
+        if self.locked_out {
+            return false;
+        }
+
+        self.energy = (self.energy - drain_rate * game_loop_duration as f64 / 1000.0).max(0.0);
+        self.locked_out = self.energy == 0.0;
+
+        true
+    }
+
+    /// Fires the weapon if it can fire, using up ammo or adding heat and starting the cooldown
+    ///
+    /// # Arguments
+    ///
+    /// * `origin` - World location of the front of the ship
+    /// * `target` - World location basic bullets are aimed at, None fires straight up. Spread shots and missiles ignore it
+    /// * `cooldown_scale` - Multiplies the cooldown started by this shot, used to speed up firing
+    ///
+    /// # Returns
+    ///
+    /// The projectiles fired, empty if the weapon couldn't fire. Energy limited weapons fire through drain_energy instead, so they never fire projectiles
+    pub fn fire(
+        &mut self,
+        origin: Point<i64>,
+        target: Option<Point<i64>>,
+        cooldown_scale: f64,
+    ) -> Vec<Bullet> {
+        if !self.can_fire() || matches!(self.limit, WeaponLimit::Energy { .. }) {
+            return vec![];
+        }
+
+        self.cooldown_remaining = (self.kind.get_cooldown() as f64 * cooldown_scale) as u128;
+
+        match self.limit {
+            WeaponLimit::None | WeaponLimit::Energy { .. } => {}
+            WeaponLimit::Ammo(_) => self.ammo -= 1,
+            WeaponLimit::Heat { per_shot, .. } => {
+                self.heat = (self.heat + per_shot).min(MAX_WEAPON_HEAT);
+                self.locked_out = self.heat >= MAX_WEAPON_HEAT;
+            }
+        }
+
+        self.get_pattern(origin.add_height(1), target)
+    }
+
+    fn get_pattern(&self, location: Point<i64>, target: Option<Point<i64>>) -> Vec<Bullet> {
+        let aimed = |location: Point<i64>| match target {
+            Some(target) => Bullet::build_aimed_bullet(location, target),
+            None => Bullet::build_basic_bullet(location),
+        };
+        let angled = |location: Point<i64>, direction: f64| {
+            let mut bullet = Bullet::build_basic_bullet(location);
+
+            bullet.drawable.velocity.width = direction * ANGLED_BULLET_SPEED;
+
+            bullet
+        };
+        // The spread sprite is centered on the ship
+        let spread = || Bullet::build_spread_bullet(location.sub_height(1).sub_width(4));
+
+        match (self.kind, self.level) {
+            (WeaponKind::Basic, 1) => vec![aimed(location)],
+            (WeaponKind::Basic, 2) => {
+                vec![aimed(location.sub_width(1)), aimed(location.add_width(1))]
+            }
+            (WeaponKind::Basic, _) => vec![
+                aimed(location),
+                angled(location, -1.0),
+                angled(location, 1.0),
+            ],
+            (WeaponKind::Spread, 1) => vec![spread()],
+            (WeaponKind::Spread, 2) => vec![spread(), aimed(location)],
+            (WeaponKind::Spread, _) => {
+                vec![spread(), angled(location, -1.0), angled(location, 1.0)]
+            }
+            // The laser is a beam, see LaserBeam
+            (WeaponKind::Laser, _) => vec![],
+            (WeaponKind::Missiles, 1) => vec![Bullet::build_homing_missile(location)],
+            (WeaponKind::Missiles, 2) => vec![
+                Bullet::build_homing_missile(location.sub_width(2)),
+                Bullet::build_homing_missile(location.add_width(2)),
+            ],
+            (WeaponKind::Missiles, _) => vec![
+                Bullet::build_homing_missile(location.sub_width(2)),
+                Bullet::build_homing_missile(location),
+                Bullet::build_homing_missile(location.add_width(2)),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::display::Point;
+
+    use super::{Weapon, WeaponKind, MAX_WEAPON_ENERGY, MAX_WEAPON_LEVEL};
+
+    const ORIGIN: Point<i64> = Point::new(20, 20);
+
+    #[test]
+    fn it_waits_for_the_cooldown_between_shots() {
+        let mut weapon = Weapon::new(WeaponKind::Basic);
+        let cooldown = WeaponKind::Basic.get_cooldown();
+
+        assert_eq!(weapon.fire(ORIGIN, None, 1.0).len(), 1);
+        assert!(weapon.fire(ORIGIN, None, 1.0).is_empty());
+
+        weapon.handle_game_loop(cooldown - 1);
+
+        assert!(!weapon.can_fire());
+
+        weapon.handle_game_loop(1);
+
+        assert_eq!(weapon.fire(ORIGIN, None, 0.5).len(), 1);
+
+        // A scaled cooldown finishes sooner
+        weapon.handle_game_loop(cooldown / 2);
+
+        assert!(weapon.can_fire());
+    }
+
+    #[test]
+    fn it_runs_out_of_ammo_until_upgraded() {
+        let mut weapon = Weapon::new(WeaponKind::Missiles);
+        let ammo = weapon.ammo;
+
+        for _ in 0..ammo {
+            assert!(!weapon.fire(ORIGIN, None, 0.0).is_empty());
+        }
+
+        assert!(weapon.fire(ORIGIN, None, 0.0).is_empty());
+
+        weapon.upgrade();
+
+        assert_eq!(weapon.ammo, ammo);
+        assert!(weapon.can_fire());
+    }
+
+    #[test]
+    fn it_overheats_until_cooled() {
+        let mut weapon = Weapon::new(WeaponKind::Spread);
+        let mut shots = 0;
+
+        while !weapon.is_locked_out() {
+            weapon.fire(ORIGIN, None, 0.0);
+            shots += 1;
+        }
+
+        assert!(shots > 1);
+        assert!(!weapon.can_fire());
+
+        // Cooling a little isn't enough, the weapon has to cool completely
+        weapon.handle_game_loop(1000);
+
+        assert!(!weapon.can_fire());
+
+        weapon.handle_game_loop(10000);
+
+        assert!(weapon.can_fire());
+    }
+
+    #[test]
+    fn it_drains_energy_until_recharged() {
+        let mut weapon = Weapon::new(WeaponKind::Laser);
+
+        // Energy weapons only fire through drain_energy
+        assert!(weapon.fire(ORIGIN, None, 0.0).is_empty());
+        assert!(!Weapon::new(WeaponKind::Basic).drain_energy(100));
+
+        let mut loops = 0;
+
+        while weapon.drain_energy(100) {
+            weapon.handle_game_loop(100);
+            loops += 1;
+        }
+
+        assert!(loops > 1);
+        assert!(weapon.is_locked_out());
+
+        // Recharging a little isn't enough, the weapon has to recharge completely
+        weapon.handle_game_loop(1000);
+
+        assert!(!weapon.drain_energy(100));
+
+        weapon.handle_game_loop(10000);
+
+        assert_eq!(weapon.energy, MAX_WEAPON_ENERGY);
+        assert!(weapon.drain_energy(100));
+    }
+
+    #[test]
+    fn it_changes_patterns_with_upgrades() {
+        let mut weapon = Weapon::new(WeaponKind::Basic);
+        let mut pattern_sizes = vec![];
+
+        loop {
+            pattern_sizes.push(weapon.fire(ORIGIN, None, 0.0).len());
+
+            if !weapon.upgrade() {
+                break;
+            }
+        }
+
+        assert_eq!(weapon.level, MAX_WEAPON_LEVEL);
+        assert_eq!(pattern_sizes, vec![1, 2, 3]);
+
+        let angled_bullets = weapon
+            .fire(ORIGIN, None, 0.0)
+            .iter()
+            .filter(|bullet| bullet.drawable.velocity.width != 0.0)
+            .count();
+
+        assert_eq!(angled_bullets, 2);
+    }
+}
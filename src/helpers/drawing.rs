@@ -27,3 +27,25 @@ pub fn get_is_position_outside_dimensions_with_offset(
 
     false
 }
+
+/// Method checks whether any part of a drawable is within the provided dimensions
+///
+/// # Arguments
+///
+/// * `dimensions` - The dimensions to check against
+/// * `location` - Top left location of the drawable
+/// * `drawable_dimensions` - Dimensions of the drawable's layout
+///
+/// # Returns
+///
+/// * True if the whole drawable is outside the dimensions
+pub fn get_is_drawable_outside_dimensions(
+    dimensions: &Point<i64>,
+    location: &Point<i64>,
+    drawable_dimensions: &Point<i64>,
+) -> bool {
+    location.width + drawable_dimensions.width <= 0
+        || location.height + drawable_dimensions.height <= 0
+        || location.width >= dimensions.width
+        || location.height >= dimensions.height
+}
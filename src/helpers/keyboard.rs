@@ -1,17 +1,59 @@
 use std::time::Duration;
 
 use crossterm::{
-    event::{poll, read, Event},
+    event::{
+        poll, read, Event, KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+    },
     Result,
 };
 
-pub fn get_keyboard_event() -> Result<Option<Event>> {
+use crate::api::display::Point;
+
+/// Waits for up to 100ms for an event, then returns it along with any other events that are already queued so none are left waiting for the next loop
+pub fn get_keyboard_events() -> Result<Vec<Event>> {
+    let mut events = vec![];
+
     // Handle keyboard presses
     if poll(Duration::from_millis(100))? {
-        let event = read()?;
+        events.push(read()?);
 
-        return Ok(Some(event));
+        while poll(Duration::from_millis(0))? {
+            events.push(read()?);
+        }
     }
 
-    Ok(None)
+    Ok(events)
+}
+
+/// Returns the key code if the event is a key being pressed or repeated, release events return None
+pub fn get_pressed_key(event: &Event) -> Option<KeyCode> {
+    match event {
+        Event::Key(KeyEvent {
+            code,
+            kind: KeyEventKind::Press | KeyEventKind::Repeat,
+            ..
+        }) => Some(*code),
+        _ => None,
+    }
+}
+
+/// Returns the screen cell of any mouse event, including movement. Terminals only report movement while mouse capture is enabled
+pub fn get_mouse_position(event: &Event) -> Option<Point<i64>> {
+    match event {
+        Event::Mouse(MouseEvent { column, row, .. }) => {
+            Some(Point::new(*column as i64, *row as i64))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the screen cell that was clicked if the event is the left mouse button being pressed
+pub fn get_mouse_click(event: &Event) -> Option<Point<i64>> {
+    match event {
+        Event::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            ..
+        }) => get_mouse_position(event),
+        _ => None,
+    }
 }
@@ -6,7 +6,23 @@ mod helpers;
 mod systems;
 mod user_display;
 
-use app::App;
+use std::{env, process};
+
+use app::{App, AppError};
+use systems::{load_level, WaveDefinition, DEFAULT_WAVES};
+
+/// Returns the waves to play, from the level file passed with `--level <path>` or the built in waves without one
+fn get_waves(args: &[String]) -> Result<Vec<WaveDefinition>, String> {
+    let Some(index) = args.iter().position(|arg| arg == "--level") else {
+        return Ok(DEFAULT_WAVES.to_vec());
+    };
+
+    let path = args
+        .get(index + 1)
+        .ok_or_else(|| "--level needs the path of a level file".to_string())?;
+
+    load_level(path).map_err(|error| format!("{}: {}", path, error))
+}
 
 // Run tests with logging: cargo test -- --nocapture
 fn main() {
@@ -15,9 +31,29 @@ fn main() {
     //     height: 40,
     // };
 
-    let mut app = App::new().unwrap();
+    // Level errors are reported before the terminal is taken over so they stay readable
+    let waves = match get_waves(&env::args().collect::<Vec<String>>()) {
+        Ok(waves) => waves,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+
+    let mut app = match App::new(waves) {
+        Ok(app) => app,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
 
-    let val = app.run();
-    dbg!("HERE");
-    dbg!(val);
+    match app.run() {
+        // Running out of lives is how a game normally ends
+        Ok(()) | Err(AppError::OutOfLives) => {}
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    }
 }
@@ -1,33 +1,32 @@
-use crate::{api::display::Point, entities::Asteroid};
-use rand::Rng;
+use crate::{
+    api::display::Point,
+    components::{CollisionLayer, Health},
+    entities::{Asteroid, AsteroidSize},
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use uuid::Uuid;
 
-use super::EntityController;
+use super::{get_bounce_velocities, EntityController};
 
 pub struct AsteroidController {
     pub entity_controller: EntityController<Asteroid>,
-    pub spawn_rate: u128,
-    // Storing this in the struct, so that the game_loop_duration can be provided each loop, this prevents fetching the system time each loop and we already have the game_loop_duration
-    time_elapsed_since_spawn: u128,
     dimensions: Point<i64>,
+    // Spawn locations and shapes all come from this so a game's asteroids can be reproduced from its seed
+    rng: StdRng,
+    // When true asteroids have generated shapes, otherwise they use the fixed sprites
+    generated_shapes: bool,
+    // When true asteroids collide with and bounce off each other, otherwise they pass through each other like the classic game
+    bounce: bool,
 }
 
-fn get_random_in_range(start: i64, end: i64) -> i64 {
-    let mut rng = rand::thread_rng();
-
-    rng.gen_range(start..end)
-}
-
-fn get_asteroid_spawn_location(dimensions: &Point<i64>) -> Point<i64> {
+fn get_asteroid_spawn_location(
+    dimensions: &Point<i64>,
+    column: Option<i64>,
+    rng: &mut impl Rng,
+) -> Point<i64> {
     Point {
         height: -3,
-        width: get_random_in_range(0, dimensions.width),
-    }
-}
-
-fn get_asteroid_velocity() -> Point<i64> {
-    Point {
-        width: get_random_in_range(-20, 20),
-        height: get_random_in_range(1, 40),
+        width: column.unwrap_or_else(|| rng.gen_range(0..dimensions.width)),
     }
 }
 
@@ -36,27 +35,118 @@ impl AsteroidController {
     ///
     /// # Arguments
     ///
-    /// * `spawn_rate` - The spawn rate of asteroids in milliseconds, essentially sets the difficulty
-    pub fn new(spawn_rate: u128, dimensions: Point<i64>) -> Self {
+    /// * `dimensions` - Dimensions of the world the asteroids fall through
+    /// * `seed` - Seed for the asteroids, the same seed always spawns the same asteroids
+    pub fn new(dimensions: Point<i64>, seed: u64) -> Self {
         Self {
-            spawn_rate,
-            time_elapsed_since_spawn: 0,
             dimensions,
+            rng: StdRng::seed_from_u64(seed),
             entity_controller: EntityController::new(),
+            generated_shapes: true,
+            bounce: false,
         }
     }
 
-    pub fn handle_game_loop(&mut self, game_loop_duration: u128) -> &mut Self {
-        self.time_elapsed_since_spawn += game_loop_duration;
+    /// Switches between generated asteroid shapes and the fixed sprites, this only applies to asteroids spawned afterwards
+    pub fn set_generated_shapes(&mut self, generated_shapes: bool) -> &mut Self {
+        self.generated_shapes = generated_shapes;
+
+        self
+    }
+
+    /// Turns bouncing between asteroids on or off, this applies to the asteroids already in the world as well as new ones
+    pub fn set_bounce(&mut self, bounce: bool) -> &mut Self {
+        self.bounce = bounce;
 
-        if self.time_elapsed_since_spawn > self.spawn_rate {
-            self.time_elapsed_since_spawn = 0;
-            self.entity_controller.spawn_entity(Asteroid::new(
-                get_asteroid_spawn_location(&self.dimensions),
-                get_asteroid_velocity(),
-            ));
+        for asteroid in self.entity_controller.get_entities_mut() {
+            Self::apply_bounce_mask(asteroid, bounce);
         }
 
         self
     }
+
+    pub fn get_bounce(&self) -> bool {
+        self.bounce
+    }
+
+    fn apply_bounce_mask(asteroid: &mut Asteroid, bounce: bool) {
+        let (_, default_mask) = CollisionLayer::get_defaults(asteroid.drawable.drawable_type);
+
+        asteroid.drawable.collision_mask = if bounce {
+            default_mask.with_layer(CollisionLayer::Enemy)
+        } else {
+            default_mask
+        };
+    }
+
+    /// Applies damage to the asteroid, destroyed asteroids break into smaller fragments
+    ///
+    /// # Returns
+    ///
+    /// The size of the asteroid if it was destroyed
+    pub fn apply_asteroid_damage(&mut self, uuid: Uuid, damage: u32) -> Option<AsteroidSize> {
+        let asteroid = self.entity_controller.get_entity(uuid)?;
+
+        let size = asteroid.size;
+        let fragments = if asteroid.get_health() <= damage {
+            asteroid.build_fragments(&mut self.rng)
+        } else {
+            vec![]
+        };
+
+        if !self.entity_controller.apply_entity_damage(uuid, damage) {
+            return None;
+        }
+
+        for mut fragment in fragments {
+            Self::apply_bounce_mask(&mut fragment, self.bounce);
+
+            self.entity_controller.spawn_entity(fragment);
+        }
+
+        Some(size)
+    }
+
+    /// Bounces two colliding asteroids off each other, asteroids that are already moving apart are left alone
+    pub fn handle_bounce(&mut self, first_uuid: Uuid, second_uuid: Uuid) -> &mut Self {
+        let velocities = match (
+            self.entity_controller.get_entity(first_uuid),
+            self.entity_controller.get_entity(second_uuid),
+        ) {
+            (Some(first), Some(second)) => get_bounce_velocities(&first.drawable, &second.drawable),
+            _ => None,
+        };
+
+        if let Some((first_velocity, second_velocity)) = velocities {
+            for (uuid, velocity) in [(first_uuid, first_velocity), (second_uuid, second_velocity)] {
+                if let Some(asteroid) = self.entity_controller.get_entity_mut(uuid) {
+                    asteroid.drawable.velocity = velocity;
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Spawns a large asteroid above the world
+    ///
+    /// # Arguments
+    ///
+    /// * `column` - World column the asteroid spawns in, None picks a random column
+    /// * `velocity` - Velocity of the asteroid in cells per second
+    pub fn spawn_asteroid(&mut self, column: Option<i64>, velocity: Point<f64>) -> &mut Self {
+        let location = get_asteroid_spawn_location(&self.dimensions, column, &mut self.rng);
+
+        let mut asteroid = if self.generated_shapes {
+            Asteroid::generate(AsteroidSize::Large, location, velocity, &mut self.rng)
+        } else {
+            Asteroid::new(AsteroidSize::Large, location, velocity)
+        };
+
+        Self::apply_bounce_mask(&mut asteroid, self.bounce);
+
+        self.entity_controller.spawn_entity(asteroid);
+
+        self
+    }
 }
@@ -0,0 +1,67 @@
+use crate::{
+    api::display::Point,
+    entities::{Boss, Bullet},
+};
+
+use super::EntityController;
+
+pub struct BossController {
+    pub entity_controller: EntityController<Boss>,
+    /// Bullets fired by every boss, these only collide with the player
+    pub bullet_entity_controller: EntityController<Bullet>,
+    dimensions: Point<i64>,
+}
+
+impl BossController {
+    pub fn new(dimensions: Point<i64>) -> Self {
+        Self {
+            entity_controller: EntityController::new(),
+            bullet_entity_controller: EntityController::new(),
+            dimensions,
+        }
+    }
+
+    /// Spawns a boss above the middle of the world
+    pub fn spawn_boss(&mut self) -> &mut Self {
+        let mut boss = Boss::new(Point::default());
+        let width = boss.drawable.layout.dimensions.width;
+
+        boss.drawable.teleport(Point::new(
+            ((self.dimensions.width - width) / 2) as f64,
+            boss.drawable.location.height,
+        ));
+
+        self.entity_controller.spawn_entity(boss);
+
+        self
+    }
+
+    /// Returns the boss being fought, None if there isn't one
+    pub fn get_active_boss(&self) -> Option<&Boss> {
+        self.entity_controller.get_entities().next()
+    }
+
+    /// Moves the bosses and fires their attacks at the target
+    ///
+    /// # Arguments
+    ///
+    /// * `game_loop_duration` - How long the loop took in milliseconds
+    /// * `target` - World location the bosses fire at, None holds their fire
+    pub fn handle_game_loop(
+        &mut self,
+        game_loop_duration: u128,
+        target: Option<Point<i64>>,
+    ) -> &mut Self {
+        let mut bullets = vec![];
+
+        for boss in self.entity_controller.get_entities_mut() {
+            bullets.append(&mut boss.handle_game_loop(game_loop_duration, self.dimensions, target));
+        }
+
+        for bullet in bullets {
+            self.bullet_entity_controller.spawn_entity(bullet);
+        }
+
+        self
+    }
+}
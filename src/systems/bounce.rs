@@ -0,0 +1,113 @@
+use crate::{api::display::Point, components::DrawableState};
+
+/// Fraction of the speed kept along the line between the drawables after a bounce, 1 is a perfectly elastic bounce
+const BOUNCE_RESTITUTION: f64 = 1.0;
+
+/// The mass of a drawable, bigger drawables push smaller ones around more than they are pushed
+pub fn get_mass(drawable_state: &DrawableState) -> f64 {
+    let dimensions = drawable_state.layout.dimensions;
+
+    (dimensions.width * dimensions.height).max(1) as f64
+}
+
+/// Returns the velocities of the drawables after they bounce off each other, None if they are already moving apart
+///
+/// # Arguments
+///
+/// * `first` - One of the colliding drawables
+/// * `second` - The other colliding drawable
+///
+/// # Returns
+///
+/// The updated velocities of the first and second drawables
+pub fn get_bounce_velocities(
+    first: &DrawableState,
+    second: &DrawableState,
+) -> Option<(Point<f64>, Point<f64>)> {
+    let offset = second.get_center() - first.get_center();
+    let distance = offset.width.hypot(offset.height);
+
+    // Drawables on top of each other have no direction to bounce in, so push them apart vertically
+    let normal = if distance == 0.0 {
+        Point::new(0.0, 1.0)
+    } else {
+        offset / Point::new(distance, distance)
+    };
+
+    let relative_velocity = second.velocity - first.velocity;
+    let approach_speed =
+        relative_velocity.width * normal.width + relative_velocity.height * normal.height;
+
+    if approach_speed >= 0.0 {
+        return None;
+    }
+
+    let first_mass = get_mass(first);
+    let second_mass = get_mass(second);
+
+    // Size of the change in momentum along the normal
+    let impulse =
+        -(1.0 + BOUNCE_RESTITUTION) * approach_speed / (1.0 / first_mass + 1.0 / second_mass);
+
+    let first_change = impulse / first_mass;
+    let second_change = impulse / second_mass;
+
+    Some((
+        first.velocity - normal * Point::new(first_change, first_change),
+        second.velocity + normal * Point::new(second_change, second_change),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+
+    use crate::{
+        api::display::{Layout, Point},
+        components::{DrawableState, DrawableType},
+    };
+
+    use super::get_bounce_velocities;
+
+    fn create_drawable(size: i64, location: Point<i64>, velocity: Point<f64>) -> DrawableState {
+        let ascii = vec!["#".repeat(size as usize); size as usize].join("\n");
+
+        DrawableState::new(
+            Layout::from_ascii(&ascii, Color::Grey),
+            location,
+            DrawableType::Enemy(1),
+            Some(velocity),
+        )
+    }
+
+    #[test]
+    fn it_swaps_velocities_for_equal_masses_moving_towards_each_other() {
+        let first = create_drawable(2, Point::new(0, 0), Point::new(10.0, 0.0));
+        let second = create_drawable(2, Point::new(2, 0), Point::new(-4.0, 0.0));
+
+        let (first_velocity, second_velocity) = get_bounce_velocities(&first, &second).unwrap();
+
+        assert_eq!(first_velocity.round(), Point::new(-4, 0));
+        assert_eq!(second_velocity.round(), Point::new(10, 0));
+    }
+
+    #[test]
+    fn it_moves_lighter_drawables_more() {
+        let small = create_drawable(1, Point::new(0, 0), Point::new(10.0, 0.0));
+        let large = create_drawable(4, Point::new(1, -1), Point::new(0.0, 0.0));
+
+        let (small_velocity, large_velocity) = get_bounce_velocities(&small, &large).unwrap();
+
+        assert!(small_velocity.width < 0.0);
+        assert!(large_velocity.width > 0.0);
+        assert!(large_velocity.width < small_velocity.width.abs());
+    }
+
+    #[test]
+    fn it_ignores_drawables_moving_apart() {
+        let first = create_drawable(2, Point::new(0, 0), Point::new(-10.0, 0.0));
+        let second = create_drawable(2, Point::new(2, 0), Point::new(4.0, 0.0));
+
+        assert_eq!(get_bounce_velocities(&first, &second), None);
+    }
+}
@@ -1,27 +1,224 @@
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, HashMap};
 
 use uuid::Uuid;
 
 use crate::{
-    api::display::{collapse_twoDVec, create_map, Point, TwoDVec},
-    components::{DrawableState, DrawableType},
+    api::display::Point,
+    components::{
+        get_can_layers_collide, CollisionLayer, CollisionMask, DrawableState, DrawableType,
+    },
 };
 
-// Enum stores the damage result of the player or ammunition and also the damage result of the asteroid
+use super::CollisionResponseTable;
+
+// Stores the damage result of the affected drawable and also the damage result of the drawable it collided with, the enemy
 pub struct CollisionOutcome {
     pub affected_damage: u32,
     pub enemy_damage: u32,
     pub asteroid_uuid: Uuid,
+    /// Every cell the drawables overlapped on
+    pub cells: Vec<Point<i64>>,
+    /// Velocity of the enemy relative to the affected drawable, in cells per second
+    pub relative_velocity: Point<f64>,
 }
 
+/// Cells where drawables overlap, along with the drawables on each cell
+type CellOverlaps = Vec<(Point<i64>, Vec<MinimalDrawableDetails>)>;
+
 #[derive(Clone, Debug, Copy)]
-struct MinimalDrawableDetails {
+pub struct MinimalDrawableDetails {
     pub uuid: Uuid,
     pub drawable_type: DrawableType,
+    pub collision_layer: CollisionLayer,
+    pub collision_mask: CollisionMask,
+    pub velocity: Point<f64>,
+}
+
+impl From<&DrawableState> for MinimalDrawableDetails {
+    fn from(drawable_state: &DrawableState) -> Self {
+        Self {
+            uuid: drawable_state.uuid,
+            velocity: drawable_state.velocity,
+            drawable_type: drawable_state.drawable_type,
+            collision_layer: drawable_state.collision_layer,
+            collision_mask: drawable_state.collision_mask,
+        }
+    }
+}
+
+/// Returns the locations a drawable occupies for collision detection. A drawable that moved further than its own size in the last loop is sampled along the path from its previous location, so it can't pass through anything between the two.
+///
+/// The samples are spaced so consecutive ones touch, every cell the drawable passed over is covered. Other drawables are swept separately, so two fast drawables whose paths cross at different times can still collide
+fn get_swept_locations(drawable_state: &DrawableState) -> Vec<Point<i64>> {
+    let location = drawable_state.get_location();
+    let previous_location = drawable_state.get_previous_location();
+    let displacement = location - previous_location;
+    let dimensions = drawable_state.layout.dimensions;
+
+    if displacement.width.abs() <= dimensions.width
+        && displacement.height.abs() <= dimensions.height
+    {
+        return vec![location];
+    }
+
+    let get_steps = |distance: i64, size: i64| (distance.abs() + size.max(1) - 1) / size.max(1);
+
+    let steps = get_steps(displacement.width, dimensions.width)
+        .max(get_steps(displacement.height, dimensions.height));
+
+    (1..=steps)
+        .map(|step| {
+            let progress = step as f64 / steps as f64;
+
+            Point::new(
+                previous_location.width + (displacement.width as f64 * progress).round() as i64,
+                previous_location.height + (displacement.height as f64 * progress).round() as i64,
+            )
+        })
+        .collect()
+}
+
+/// Width and height of the cells grouped into each spatial hash bucket
+const BUCKET_SIZE: i64 = 8;
+
+/// A drawable prepared for the broad phase
+struct BroadPhaseItem<'a> {
+    drawable_state: &'a DrawableState,
+    /// Bounding box of the occupied cells. The end is exclusive
+    start: Point<i64>,
+    end: Point<i64>,
+    /// Whether each cell of the bounding box is covered by an element of the layout at any of the swept locations, row by row. This is worked out once so overlaps are a lookup
+    occupied: Vec<bool>,
+}
+
+impl<'a> BroadPhaseItem<'a> {
+    /// Returns None if the drawable doesn't cover any cell inside the dimensions
+    fn new(drawable_state: &'a DrawableState, dimensions: &Point<i64>) -> Option<Self> {
+        let locations = get_swept_locations(drawable_state);
+        let drawable_dimensions = drawable_state.layout.dimensions;
+
+        let start = locations.iter().fold(*dimensions, |start, location| {
+            Point::new(
+                start.width.min(location.width.max(0)),
+                start.height.min(location.height.max(0)),
+            )
+        });
+        let end = locations.iter().fold(Point::new(0, 0), |end, location| {
+            let location_end = *location + drawable_dimensions;
+
+            Point::new(
+                end.width.max(location_end.width.min(dimensions.width)),
+                end.height.max(location_end.height.min(dimensions.height)),
+            )
+        });
+
+        if start.width >= end.width || start.height >= end.height {
+            return None;
+        }
+
+        let mut item = Self {
+            drawable_state,
+            start,
+            end,
+            occupied: vec![
+                false;
+                ((end.width - start.width) * (end.height - start.height)) as usize
+            ],
+        };
+
+        for location in locations {
+            for (height, row) in drawable_state.layout.map.iter().enumerate() {
+                for (width, element) in row.iter().enumerate() {
+                    let cell = location + Point::new(width as i64, height as i64);
+
+                    if element.is_some() {
+                        if let Some(index) = item.get_index(&cell) {
+                            item.occupied[index] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        Some(item)
+    }
+
+    /// Returns the index of the cell in occupied, None for cells outside the bounding box
+    fn get_index(&self, cell: &Point<i64>) -> Option<usize> {
+        if cell.width < self.start.width
+            || cell.width >= self.end.width
+            || cell.height < self.start.height
+            || cell.height >= self.end.height
+        {
+            return None;
+        }
+
+        let offset = *cell - self.start;
+
+        Some((offset.height * (self.end.width - self.start.width) + offset.width) as usize)
+    }
+
+    fn get_is_overlapping(&self, other: &BroadPhaseItem) -> bool {
+        self.start.width < other.end.width
+            && other.start.width < self.end.width
+            && self.start.height < other.end.height
+            && other.start.height < self.end.height
+    }
+
+    fn get_is_occupying(&self, cell: &Point<i64>) -> bool {
+        self.get_index(cell)
+            .is_some_and(|index| self.occupied[index])
+    }
+
+    fn get_details(&self) -> MinimalDrawableDetails {
+        MinimalDrawableDetails::from(self.drawable_state)
+    }
+}
+
+fn get_bucket(cell: &Point<i64>) -> Point<i64> {
+    Point::new(cell.width / BUCKET_SIZE, cell.height / BUCKET_SIZE)
+}
+
+/// Returns every pair of items whose bounding boxes overlap, once for each spatial hash bucket they share. Pairs are grouped by bucket
+fn get_candidate_pairs(items: &[BroadPhaseItem]) -> Vec<(Point<i64>, usize, usize)> {
+    // Each item is listed once for every bucket it touches, sorting groups the items in a bucket together. Only buckets holding an item take up space
+    let mut bucket_items: Vec<((i64, i64), usize)> = vec![];
+
+    for (index, item) in items.iter().enumerate() {
+        let start_bucket = get_bucket(&item.start);
+        let end_bucket = get_bucket(&(item.end - Point::new(1, 1)));
+
+        for height in start_bucket.height..=end_bucket.height {
+            for width in start_bucket.width..=end_bucket.width {
+                bucket_items.push(((height, width), index));
+            }
+        }
+    }
+
+    bucket_items.sort_unstable();
+
+    let mut candidate_pairs: Vec<(Point<i64>, usize, usize)> = vec![];
+
+    for bucket_group in bucket_items.chunk_by(|first, second| first.0 == second.0) {
+        let (height, width) = bucket_group[0].0;
+        let bucket = Point::new(width, height);
+
+        for (position, (_, first)) in bucket_group.iter().enumerate() {
+            for (_, second) in bucket_group.iter().skip(position + 1) {
+                if items[*first].get_is_overlapping(&items[*second]) {
+                    candidate_pairs.push((bucket, *first, *second));
+                }
+            }
+        }
+    }
+
+    candidate_pairs
 }
 
 /// This method calculates the positions where the provided drawable_items overlap. It returns an array where each item is a position on the grid with overlapping drawable_items, it returns the uuid and drawable_type for each item.
 ///
+/// A broad phase first finds pairs of drawables that could overlap using their bounding boxes and a spatial hash, only those pairs have their occupied cells compared, one bucket at a time. Nothing is sized by the dimensions, so the cost follows the number of drawables rather than the size of the screen. Each drawable works out the cells it occupies once, so comparing a cell is a lookup rather than a scan of its layout
+///
 /// # Arguments
 ///
 /// * `drawable_items` - The drawable items to check for overlaps
@@ -31,54 +228,153 @@ struct MinimalDrawableDetails {
 fn get_positions_with_overlaps(
     drawable_items: Vec<&DrawableState>,
     dimensions: &Point<i64>,
-) -> TwoDVec<MinimalDrawableDetails> {
-    let mut parsed_map: TwoDVec<Option<Vec<MinimalDrawableDetails>>> = create_map(dimensions, None);
-
-    // Iterating over each drawable item to handle it
-    for drawable_state in drawable_items {
-        // Each row in the drawable layout
-        for (index, row) in drawable_state.layout.map.iter().enumerate() {
-            let height = drawable_state.location.height + index as i64;
+) -> CellOverlaps {
+    let items: Vec<BroadPhaseItem> = drawable_items
+        .into_iter()
+        // Background drawables are purely visual
+        .filter(|drawable_state| drawable_state.drawable_type != DrawableType::Background)
+        .filter_map(|drawable_state| BroadPhaseItem::new(drawable_state, dimensions))
+        .collect();
+
+    let mut overlaps: CellOverlaps = vec![];
+    // The index in overlaps of each cell in the current bucket, row by row. Buckets don't share cells so this is cleared between them
+    let mut cell_indexes: [Option<usize>; (BUCKET_SIZE * BUCKET_SIZE) as usize] =
+        [None; (BUCKET_SIZE * BUCKET_SIZE) as usize];
+    let mut current_bucket: Option<Point<i64>> = None;
+
+    for (bucket, first, second) in get_candidate_pairs(&items) {
+        if current_bucket != Some(bucket) {
+            current_bucket = Some(bucket);
+            cell_indexes = [None; (BUCKET_SIZE * BUCKET_SIZE) as usize];
+        }
 
-            if height < 0 || height >= dimensions.height {
-                continue;
-            }
+        let (first_item, second_item) = (&items[first], &items[second]);
+        let bucket_start = bucket * Point::new(BUCKET_SIZE, BUCKET_SIZE);
+
+        // Only the cells inside both bounding boxes and the bucket can overlap, the rest of the overlap is handled with the other buckets the pair shares
+        for height in first_item
+            .start
+            .height
+            .max(second_item.start.height)
+            .max(bucket_start.height)
+            ..first_item
+                .end
+                .height
+                .min(second_item.end.height)
+                .min(bucket_start.height + BUCKET_SIZE)
+        {
+            for width in first_item
+                .start
+                .width
+                .max(second_item.start.width)
+                .max(bucket_start.width)
+                ..first_item
+                    .end
+                    .width
+                    .min(second_item.end.width)
+                    .min(bucket_start.width + BUCKET_SIZE)
+            {
+                let cell = Point::new(width, height);
 
-            for (width, element) in row.iter().enumerate() {
-                if element.is_none() {
+                if !first_item.get_is_occupying(&cell) || !second_item.get_is_occupying(&cell) {
                     continue;
                 }
 
-                let width = drawable_state.location.width + width as i64;
+                let cell_index =
+                    &mut cell_indexes[((height - bucket_start.height) * BUCKET_SIZE + width
+                        - bucket_start.width) as usize];
+                let overlap_index = *cell_index.get_or_insert_with(|| {
+                    overlaps.push((cell, Vec::with_capacity(2)));
+
+                    overlaps.len() - 1
+                });
+                let cell_items = &mut overlaps[overlap_index].1;
+
+                for item in [first_item, second_item] {
+                    if cell_items
+                        .iter()
+                        .all(|existing| existing.uuid != item.drawable_state.uuid)
+                    {
+                        cell_items.push(item.get_details());
+                    }
+                }
+            }
+        }
+    }
+
+    overlaps
+}
+
+/// The original narrow phase only implementation, every cell of the dimensions is allocated and checked. This is kept to check the broad phase against and to benchmark it.
+///
+/// This method calculates the positions where the provided drawable_items overlap. It returns an array where each item is a position on the grid with overlapping drawable_items, it returns the uuid and drawable_type for each item.
+///
+/// # Arguments
+///
+/// * `drawable_items` - The drawable items to check for overlaps
+/// * `dimensions` - The game dimensions, ignoring outside of dimensions
+///
+/// ```
+#[cfg(test)]
+fn get_positions_with_overlaps_in_grid(
+    drawable_items: Vec<&DrawableState>,
+    dimensions: &Point<i64>,
+) -> CellOverlaps {
+    let mut parsed_map: crate::api::display::TwoDVec<Option<Vec<MinimalDrawableDetails>>> =
+        crate::api::display::create_map(dimensions, None);
+
+    // Iterating over each drawable item to handle it
+    for drawable_state in drawable_items {
+        // Background drawables are purely visual
+        if drawable_state.drawable_type == DrawableType::Background {
+            continue;
+        }
 
-                if width < 0 || width >= dimensions.width {
+        let parsed_item = MinimalDrawableDetails::from(drawable_state);
+
+        for location in get_swept_locations(drawable_state) {
+            // Each row in the drawable layout
+            for (index, row) in drawable_state.layout.map.iter().enumerate() {
+                let height = location.height + index as i64;
+
+                if height < 0 || height >= dimensions.height {
                     continue;
                 }
 
-                let parsed_item = MinimalDrawableDetails {
-                    uuid: drawable_state.uuid,
-                    drawable_type: drawable_state.drawable_type,
-                };
-
-                match &mut parsed_map[height as usize][width as usize] {
-                    Some(items) => {
-                        items.push(parsed_item);
+                for (width, element) in row.iter().enumerate() {
+                    if element.is_none() {
+                        continue;
                     }
-                    None => {
-                        parsed_map[height as usize][width as usize] = Some(vec![parsed_item]);
+
+                    let width = location.width + width as i64;
+
+                    if width < 0 || width >= dimensions.width {
+                        continue;
                     }
-                };
+
+                    match &mut parsed_map[height as usize][width as usize] {
+                        Some(items) => {
+                            // Swept locations overlap, so the drawable may already be in this cell
+                            if items.iter().all(|item| item.uuid != parsed_item.uuid) {
+                                items.push(parsed_item);
+                            }
+                        }
+                        None => {
+                            parsed_map[height as usize][width as usize] = Some(vec![parsed_item]);
+                        }
+                    };
+                }
             }
         }
     }
 
     // Reduce the map into a single array of overlapping drawable_items
-    let mut drawable_details: TwoDVec<MinimalDrawableDetails> = vec![];
+    let mut drawable_details: CellOverlaps = vec![];
 
-    for item in collapse_twoDVec(parsed_map).iter_mut() {
-        if let Some(some_item) = item {
-            if some_item.len() > 1 {
-                drawable_details.push(some_item.to_vec());
+    for (height, row) in parsed_map.into_iter().enumerate() {
+        for (width, some_item) in row.into_iter().enumerate() {
+            if let Some(items) = some_item.filter(|items| items.len() > 1) {
+                drawable_details.push((Point::new(width as i64, height as i64), items));
             }
         }
     }
@@ -93,78 +389,59 @@ type CollisionResults = HashMap<Uuid, HashMap<Uuid, CollisionOutcome>>;
 /// How much damage to apply to an enemy when it collides with a player, this should destroy the enemy if a collision occurs
 pub const PLAYER_ENEMY_COLLISION_DAMAGE: u32 = 100;
 
+/// Finds every collision between drawables whose layers collide, the response table decides the damage each drawable takes
+///
+/// # Arguments
+///
+/// * `drawable_items` - The drawable items to check for collisions
+/// * `dimensions` - The game dimensions, ignoring outside of dimensions
+/// * `collision_responses` - Responses for each pair of layers, layers without a response don't affect each other even if they collide
 pub fn run_collision_detection(
     drawable_items: Vec<&DrawableState>,
     dimensions: &Point<i64>,
+    collision_responses: &CollisionResponseTable,
 ) -> CollisionResults {
     let mut collision_outcomes: HashMap<Uuid, HashMap<Uuid, CollisionOutcome>> = HashMap::new();
 
     let positions_with_overlaps = get_positions_with_overlaps(drawable_items, dimensions);
 
-    for elements_on_position in positions_with_overlaps {
-        // Single out all the enemies, so that if there is a player or ammunition on the position we can apply affects
-        let mut enemies: Vec<&MinimalDrawableDetails> = vec![];
-        for element in &elements_on_position {
-            match element.drawable_type {
-                DrawableType::Enemy(..) => {
-                    enemies.push(element);
+    for (cell, elements_on_position) in positions_with_overlaps {
+        for (index, first) in elements_on_position.iter().enumerate() {
+            for second in elements_on_position.iter().skip(index + 1) {
+                if !get_can_layers_collide(
+                    (first.collision_layer, first.collision_mask),
+                    (second.collision_layer, second.collision_mask),
+                ) {
+                    continue;
                 }
-                _ => {}
-            }
-        }
-
-        for element in &elements_on_position {
-            match element.drawable_type {
-                DrawableType::Player | DrawableType::Ammunition(..) => {
-                    // Get the hashmap for element collisions for the player
-                    let element_collisions = match collision_outcomes.get_mut(&element.uuid) {
-                        // Return the hashmap if it exists
-                        Some(collisions) => collisions,
-                        // Otherwise create it then return a mutable reference to it
-                        None => {
-                            collision_outcomes.insert(element.uuid, HashMap::new());
 
-                            collision_outcomes.get_mut(&element.uuid).unwrap()
-                        }
-                    };
+                let Some((response, affected, other)) =
+                    collision_responses.get_response(first, second)
+                else {
+                    continue;
+                };
 
-                    for enemy in &enemies {
-                        // Only create a new collision if one doesn't exist already for the same element/enemy combination
-                        if !element_collisions.contains_key(&enemy.uuid) {
-                            if let DrawableType::Enemy(enemy_damage) = enemy.drawable_type {
-                                // If an enemy hit the player, apply the enemies damage to the player and destroy the enemy
-                                if element.drawable_type == DrawableType::Player {
-                                    element_collisions.insert(
-                                        enemy.uuid,
-                                        CollisionOutcome {
-                                            affected_damage: enemy_damage,
-                                            enemy_damage: PLAYER_ENEMY_COLLISION_DAMAGE,
-                                            asteroid_uuid: enemy.uuid,
-                                        },
-                                    );
-                                } else {
-                                    // If the asteroid collided with ammunition, extract the ammunition damage
-                                    if let DrawableType::Ammunition(ammunition_damage) =
-                                        element.drawable_type
-                                    {
-                                        // Apply the enemies damage to the ammunition and the ammunition's damage to the enemy
-                                        element_collisions.insert(
-                                            enemy.uuid,
-                                            CollisionOutcome {
-                                                affected_damage: enemy_damage,
-                                                enemy_damage: ammunition_damage,
-                                                asteroid_uuid: enemy.uuid,
-                                            },
-                                        );
-                                    }
-                                }
-                            } else {
-                                panic!("Error: Not an enemy in the enemies vector");
-                            }
-                        }
+                // Only create a new collision if one doesn't exist already for the same pair, they usually overlap on several positions
+                match collision_outcomes
+                    .entry(affected.uuid)
+                    .or_default()
+                    .entry(other.uuid)
+                {
+                    Entry::Vacant(entry) => {
+                        let damage = response(affected, other);
+
+                        entry.insert(CollisionOutcome {
+                            affected_damage: damage.affected_damage,
+                            enemy_damage: damage.other_damage,
+                            asteroid_uuid: other.uuid,
+                            cells: vec![cell],
+                            relative_velocity: other.velocity - affected.velocity,
+                        });
+                    }
+                    Entry::Occupied(mut entry) => {
+                        entry.get_mut().cells.push(cell);
                     }
                 }
-                _ => {}
             }
         }
     }
@@ -172,6 +449,52 @@ pub fn run_collision_detection(
     collision_outcomes
 }
 
+/// A single collision between two drawables, this is for reacting to where and how drawables hit each other. Damage should be applied from the collision summary
+#[derive(Debug, Clone, PartialEq)]
+pub struct CollisionEvent {
+    pub affected_uuid: Uuid,
+    pub other_uuid: Uuid,
+    pub affected_damage: u32,
+    pub other_damage: u32,
+    /// Every cell the drawables overlapped on, sorted from the top left
+    pub cells: Vec<Point<i64>>,
+    /// The center of the overlapping cells
+    pub contact_point: Point<f64>,
+    /// Velocity of the other drawable relative to the affected drawable, in cells per second
+    pub relative_velocity: Point<f64>,
+}
+
+/// Flattens the collision results into one event for each pair of drawables that collided
+pub fn get_collision_events(collision_results: &CollisionResults) -> Vec<CollisionEvent> {
+    let mut collision_events = vec![];
+
+    for (affected_uuid, element_collisions) in collision_results {
+        for (other_uuid, outcome) in element_collisions {
+            let mut cells = outcome.cells.clone();
+
+            cells.sort_by_key(|cell| (cell.height, cell.width));
+
+            let cell_count = cells.len().max(1) as f64;
+            let cell_total = cells.iter().fold(Point::new(0.0, 0.0), |total, cell| {
+                // Use the center of each cell
+                total + Point::from(*cell) + Point::new(0.5, 0.5)
+            });
+
+            collision_events.push(CollisionEvent {
+                affected_uuid: *affected_uuid,
+                other_uuid: *other_uuid,
+                affected_damage: outcome.affected_damage,
+                other_damage: outcome.enemy_damage,
+                cells,
+                contact_point: cell_total / Point::new(cell_count, cell_count),
+                relative_velocity: outcome.relative_velocity,
+            });
+        }
+    }
+
+    collision_events
+}
+
 pub struct Summary {
     pub uuid: Uuid,
     pub damage: u32,
@@ -214,15 +537,105 @@ pub fn get_collision_summary(collision_results: CollisionResults) -> CollisionSu
 
 #[cfg(test)]
 mod tests {
+    use std::time::Instant;
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use uuid::Uuid;
 
     use crate::{
-        api::display::{Map, Point, TwoDVec},
-        components::Drawable,
-        entities::{player, Asteroid, Bullet, Player, ASTEROID_DAMAGE, BULLET_DAMAGE},
-        systems::PLAYER_ENEMY_COLLISION_DAMAGE,
+        api::display::Point,
+        components::{CollisionLayer, Drawable, DrawableState},
+        entities::{Asteroid, AsteroidSize, Bullet, Player, BULLET_DAMAGE},
+        systems::{
+            CollisionDamage, CollisionResponseTable, Starfield, PLAYER_ENEMY_COLLISION_DAMAGE,
+        },
+    };
+
+    use super::{
+        get_collision_events, get_collision_summary, get_positions_with_overlaps,
+        get_positions_with_overlaps_in_grid, run_collision_detection, CellOverlaps,
+        CollisionResults,
     };
 
-    use super::{get_collision_summary, get_positions_with_overlaps, run_collision_detection};
+    fn run_collision_detection_with_defaults(
+        drawable_items: Vec<&DrawableState>,
+        dimensions: &Point<i64>,
+    ) -> CollisionResults {
+        run_collision_detection(
+            drawable_items,
+            dimensions,
+            &CollisionResponseTable::default(),
+        )
+    }
+
+    const SCENE_DIMENSIONS: Point<i64> = Point::new(200, 60);
+
+    /// Creates a random mix of asteroids and bullets, some of the bullets have moved far enough in the last loop to be swept
+    fn get_random_scene(entity_count: usize, seed: u64) -> (Vec<Asteroid>, Vec<Bullet>) {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let get_location = |rng: &mut StdRng| {
+            Point::new(
+                rng.gen_range(-5..SCENE_DIMENSIONS.width),
+                rng.gen_range(-5..SCENE_DIMENSIONS.height),
+            )
+        };
+
+        let asteroids = (0..entity_count / 2)
+            .map(|_| {
+                Asteroid::new(
+                    AsteroidSize::Medium,
+                    get_location(&mut rng),
+                    Point::default(),
+                )
+            })
+            .collect();
+
+        let bullets = (0..entity_count - entity_count / 2)
+            .map(|_| {
+                let mut bullet = Bullet::build_basic_bullet(get_location(&mut rng));
+                let distance = rng.gen_range(0..10) as f64;
+
+                bullet
+                    .drawable
+                    .set_location(bullet.drawable.location.sub_height(distance));
+
+                bullet
+            })
+            .collect();
+
+        (asteroids, bullets)
+    }
+
+    fn get_scene_drawable_states<'a>(
+        asteroids: &'a [Asteroid],
+        bullets: &'a [Bullet],
+    ) -> Vec<&'a DrawableState> {
+        asteroids
+            .iter()
+            .map(|asteroid| asteroid.get_drawable_state())
+            .chain(bullets.iter().map(|bullet| bullet.get_drawable_state()))
+            .collect()
+    }
+
+    /// Sorts the overlaps so results with the same cells in a different order are equal
+    fn get_sorted_overlaps(overlaps: CellOverlaps) -> Vec<(i64, i64, Vec<Uuid>)> {
+        let mut sorted_overlaps: Vec<(i64, i64, Vec<Uuid>)> = overlaps
+            .into_iter()
+            .map(|(cell, details)| {
+                let mut uuids: Vec<Uuid> =
+                    details.into_iter().map(|details| details.uuid).collect();
+
+                uuids.sort();
+
+                (cell.height, cell.width, uuids)
+            })
+            .collect();
+
+        sorted_overlaps.sort();
+
+        sorted_overlaps
+    }
 
     const POSITION: Point<i64> = Point {
         width: 5,
@@ -231,23 +644,21 @@ mod tests {
 
     fn get_asteroid_mock() -> Asteroid {
         Asteroid::new(
+            AsteroidSize::Medium,
             POSITION,
             Point {
-                width: 1,
-                height: 1,
+                width: 1.0,
+                height: 1.0,
             },
         )
     }
 
-    /**
-     *
-     */
     #[test]
     fn it_should_return_no_collisions_when_providing_multiple_asteroids() {
         let asteroid = get_asteroid_mock();
         let drawable_states = vec![asteroid.get_drawable_state(); 10];
 
-        let collisions = run_collision_detection(
+        let collisions = run_collision_detection_with_defaults(
             drawable_states,
             &Point {
                 width: 30,
@@ -258,9 +669,37 @@ mod tests {
         assert_eq!(collisions.len(), 0);
     }
 
-    /**
-     *
-     */
+    #[test]
+    fn it_should_return_harmless_collisions_for_asteroids_that_bounce() {
+        let mut first = get_asteroid_mock();
+        let mut second = Asteroid::new(
+            AsteroidSize::Medium,
+            POSITION.add_width(1),
+            Point::new(-1.0, 1.0),
+        );
+
+        for asteroid in [&mut first, &mut second] {
+            asteroid.drawable.collision_mask = asteroid
+                .drawable
+                .collision_mask
+                .with_layer(CollisionLayer::Enemy);
+        }
+
+        let collisions = run_collision_detection_with_defaults(
+            vec![first.get_drawable_state(), second.get_drawable_state()],
+            &Point {
+                width: 30,
+                height: 30,
+            },
+        );
+
+        let collision_events = get_collision_events(&collisions);
+
+        assert_eq!(collision_events.len(), 1);
+        assert_eq!(collision_events[0].affected_damage, 0);
+        assert_eq!(collision_events[0].other_damage, 0);
+    }
+
     #[test]
     fn it_should_return_no_collisions_when_providing_player_and_ammunition() {
         let player = Player::new(Some(POSITION));
@@ -268,7 +707,7 @@ mod tests {
 
         let drawable_states = vec![player.get_drawable_state(), ammunition.get_drawable_state()];
 
-        let collisions = run_collision_detection(
+        let collisions = run_collision_detection_with_defaults(
             drawable_states,
             &Point {
                 width: 30,
@@ -279,9 +718,6 @@ mod tests {
         assert_eq!(collisions.len(), 0);
     }
 
-    /**
-     *
-     */
     #[test]
     fn it_should_return_a_collision_for_a_player_and_asteroid() {
         let player = Player::new(Some(POSITION));
@@ -290,7 +726,7 @@ mod tests {
 
         let drawable_states = vec![asteroid.get_drawable_state(), player.get_drawable_state()];
 
-        let collisions = run_collision_detection(
+        let collisions = run_collision_detection_with_defaults(
             drawable_states,
             &Point {
                 width: 30,
@@ -316,9 +752,98 @@ mod tests {
         );
     }
 
-    /**
-     *
-     */
+    #[test]
+    fn it_should_return_a_collision_for_ammunition_that_moved_through_an_asteroid() {
+        let asteroid = get_asteroid_mock();
+
+        let mut ammunition = Bullet::build_basic_bullet(Point::new(7, 20));
+
+        // Teleporting has no path, so the ammunition is entirely above the asteroid
+        ammunition.drawable.teleport(Point::new(7.0, 0.0));
+
+        let dimensions = Point::new(30, 30);
+
+        let collisions = run_collision_detection_with_defaults(
+            vec![
+                asteroid.get_drawable_state(),
+                ammunition.get_drawable_state(),
+            ],
+            &dimensions,
+        );
+
+        assert!(collisions.is_empty());
+
+        // Moving the same distance in a single loop passes through the asteroid
+        ammunition.drawable.teleport(Point::new(7.0, 20.0));
+        ammunition.drawable.set_location(Point::new(7.0, 0.0));
+
+        let collisions = run_collision_detection_with_defaults(
+            vec![
+                asteroid.get_drawable_state(),
+                ammunition.get_drawable_state(),
+            ],
+            &dimensions,
+        );
+
+        assert!(collisions
+            .get(&ammunition.drawable.uuid)
+            .unwrap()
+            .contains_key(&asteroid.drawable.uuid));
+    }
+
+    #[test]
+    fn it_should_use_the_response_table_for_new_layers() {
+        let mut player = Player::new(Some(POSITION));
+        // Lines the ammunition up with the tip of the ship
+        let mut ammunition = Bullet::build_basic_bullet(POSITION.add_width(6));
+
+        // Friendly fire, the player's ammunition can now hit the player
+        player.drawable.collision_mask = player
+            .drawable
+            .collision_mask
+            .with_layer(CollisionLayer::PlayerAmmunition);
+        ammunition.drawable.collision_mask = ammunition
+            .drawable
+            .collision_mask
+            .with_layer(CollisionLayer::Player);
+
+        let dimensions = Point::new(30, 30);
+
+        // Without a response the layers collide but nothing happens
+        let collisions = run_collision_detection_with_defaults(
+            vec![player.get_drawable_state(), ammunition.get_drawable_state()],
+            &dimensions,
+        );
+
+        assert!(collisions.is_empty());
+
+        let mut collision_responses = CollisionResponseTable::default();
+
+        collision_responses.set_response(
+            CollisionLayer::Player,
+            CollisionLayer::PlayerAmmunition,
+            |_, _| CollisionDamage {
+                affected_damage: 2,
+                other_damage: 1,
+            },
+        );
+
+        let collisions = run_collision_detection(
+            vec![player.get_drawable_state(), ammunition.get_drawable_state()],
+            &dimensions,
+            &collision_responses,
+        );
+
+        let player_collision = collisions
+            .get(&player.drawable.uuid)
+            .unwrap()
+            .get(&ammunition.drawable.uuid)
+            .unwrap();
+
+        assert_eq!(player_collision.affected_damage, 2);
+        assert_eq!(player_collision.enemy_damage, 1);
+    }
+
     #[test]
     fn it_should_return_a_collision_for_a_asteroid_and_ammunition() {
         let ammunition = Bullet::build_basic_bullet(POSITION);
@@ -330,7 +855,7 @@ mod tests {
             ammunition.get_drawable_state(),
         ];
 
-        let collisions = run_collision_detection(
+        let collisions = run_collision_detection_with_defaults(
             drawable_states,
             &Point {
                 width: 30,
@@ -350,16 +875,58 @@ mod tests {
         // Expect that the bullet took damange
         assert_eq!(
             ammunition_asteroid_collision.affected_damage,
-            ASTEROID_DAMAGE
+            AsteroidSize::Medium.get_damage()
         );
 
         // Expect that the asteroid took damage equal to the bullets damage
         assert_eq!(ammunition_asteroid_collision.enemy_damage, BULLET_DAMAGE);
     }
 
-    /**
-     *
-     */
+    #[test]
+    fn it_should_return_collision_events_with_the_contact_point_and_relative_velocity() {
+        let ammunition = Bullet::build_basic_bullet(POSITION);
+
+        let asteroid = get_asteroid_mock();
+
+        let collisions = run_collision_detection_with_defaults(
+            vec![
+                asteroid.get_drawable_state(),
+                ammunition.get_drawable_state(),
+            ],
+            &Point {
+                width: 30,
+                height: 30,
+            },
+        );
+
+        let collision_events = get_collision_events(&collisions);
+
+        assert_eq!(collision_events.len(), 1);
+
+        let collision_event = &collision_events[0];
+
+        assert_eq!(collision_event.affected_uuid, ammunition.drawable.uuid);
+        assert_eq!(collision_event.other_uuid, asteroid.drawable.uuid);
+        assert_eq!(collision_event.other_damage, BULLET_DAMAGE);
+
+        // The bullet is a single column so the contact point is in the middle of that column
+        let bullet_column = collision_event.cells[0].width;
+
+        assert!(collision_event
+            .cells
+            .iter()
+            .all(|cell| cell.width == bullet_column));
+        assert_eq!(
+            collision_event.contact_point.width,
+            bullet_column as f64 + 0.5
+        );
+
+        assert_eq!(
+            collision_event.relative_velocity,
+            asteroid.drawable.velocity - ammunition.drawable.velocity
+        );
+    }
+
     #[test]
     fn it_should_return_a_single_collision_for_player_and_multiple_occurrences_of_asteroid() {
         let player = Player::new(Some(POSITION));
@@ -371,7 +938,7 @@ mod tests {
         let mut asteroids = vec![asteroid.get_drawable_state(); 10];
         drawable_states.append(&mut asteroids);
 
-        let collisions = run_collision_detection(
+        let collisions = run_collision_detection_with_defaults(
             drawable_states,
             &Point {
                 width: 30,
@@ -386,6 +953,18 @@ mod tests {
         assert_eq!(player_collisions.len(), 1);
     }
 
+    #[test]
+    fn it_should_ignore_background_drawables() {
+        let player = Player::new(Some(POSITION));
+        let starfield = Starfield::new(Point::new(30, 30), 0);
+
+        let drawable_states = vec![player.get_drawable_state(), starfield.get_drawable_state()];
+
+        let overlaps = get_positions_with_overlaps(drawable_states, &Point::new(30, 30));
+
+        assert_eq!(overlaps.len(), 0);
+    }
+
     #[test]
     fn it_should_return_collisions_and_summarize_for_a_player_ammunition_and_multiple_asteroids() {
         let player = Player::new(Some(POSITION));
@@ -400,7 +979,7 @@ mod tests {
             ammunition.get_drawable_state(),
         ];
 
-        let collisions = run_collision_detection(
+        let collisions = run_collision_detection_with_defaults(
             drawable_states,
             &Point {
                 width: 30,
@@ -415,7 +994,7 @@ mod tests {
         for (_, collision) in collision_summary {
             if collision.uuid == player.drawable.uuid || collision.uuid == ammunition.drawable.uuid
             {
-                assert_eq!(collision.damage, ASTEROID_DAMAGE * 2);
+                assert_eq!(collision.damage, AsteroidSize::Medium.get_damage() * 2);
             } else if collision.uuid == asteroid1.drawable.uuid
                 || collision.uuid == asteroid2.drawable.uuid
             {
@@ -429,4 +1008,61 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn it_should_find_the_same_overlaps_as_checking_every_cell() {
+        for seed in 0..5 {
+            let (asteroids, bullets) = get_random_scene(200, seed);
+
+            let broad_phase_overlaps = get_sorted_overlaps(get_positions_with_overlaps(
+                get_scene_drawable_states(&asteroids, &bullets),
+                &SCENE_DIMENSIONS,
+            ));
+            let grid_overlaps = get_sorted_overlaps(get_positions_with_overlaps_in_grid(
+                get_scene_drawable_states(&asteroids, &bullets),
+                &SCENE_DIMENSIONS,
+            ));
+
+            assert!(!grid_overlaps.is_empty());
+            assert_eq!(broad_phase_overlaps, grid_overlaps);
+        }
+    }
+
+    /// Compares the broad phase with checking every cell, run with `cargo test --release -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn it_benchmarks_the_broad_phase_against_checking_every_cell() {
+        const ITERATIONS: u32 = 200;
+
+        for entity_count in [10, 100, 1000] {
+            let (asteroids, bullets) = get_random_scene(entity_count, entity_count as u64);
+
+            let start = Instant::now();
+
+            for _ in 0..ITERATIONS {
+                get_positions_with_overlaps(
+                    get_scene_drawable_states(&asteroids, &bullets),
+                    &SCENE_DIMENSIONS,
+                );
+            }
+
+            let broad_phase_duration = start.elapsed() / ITERATIONS;
+
+            let start = Instant::now();
+
+            for _ in 0..ITERATIONS {
+                get_positions_with_overlaps_in_grid(
+                    get_scene_drawable_states(&asteroids, &bullets),
+                    &SCENE_DIMENSIONS,
+                );
+            }
+
+            let grid_duration = start.elapsed() / ITERATIONS;
+
+            println!(
+                "{} entities: broad phase {:?}, every cell {:?}",
+                entity_count, broad_phase_duration, grid_duration
+            );
+        }
+    }
 }
@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use crate::components::CollisionLayer;
+
+use super::{MinimalDrawableDetails, PLAYER_ENEMY_COLLISION_DAMAGE};
+
+/// Damage dealt to each drawable in a collision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollisionDamage {
+    /// Damage to the drawable on the first layer of the response
+    pub affected_damage: u32,
+    /// Damage to the drawable on the second layer of the response
+    pub other_damage: u32,
+}
+
+/// Decides what happens when a drawable on the first layer collides with a drawable on the second layer
+///
+/// # Arguments
+///
+/// * The drawable on the first layer of the response
+/// * The drawable on the second layer of the response
+pub type CollisionResponse =
+    fn(&MinimalDrawableDetails, &MinimalDrawableDetails) -> CollisionDamage;
+
+/// Maps pairs of layers to the response for a collision between them. Each pair only needs adding once, the order of the layers decides which drawable is passed first to the response
+pub struct CollisionResponseTable {
+    responses: HashMap<(CollisionLayer, CollisionLayer), CollisionResponse>,
+}
+
+impl Default for CollisionResponseTable {
+    /// Players are damaged by enemies and destroy them on contact, player ammunition and enemies damage each other. Players use up pickups they touch. Enemies only collide with each other when their mask includes the enemy layer, which is used for bouncing so no damage is dealt
+    fn default() -> Self {
+        let mut table = Self {
+            responses: HashMap::new(),
+        };
+
+        table
+            .set_response(CollisionLayer::Player, CollisionLayer::Enemy, |_, enemy| {
+                CollisionDamage {
+                    affected_damage: enemy.drawable_type.get_damage(),
+                    other_damage: PLAYER_ENEMY_COLLISION_DAMAGE,
+                }
+            })
+            .set_response(
+                CollisionLayer::PlayerAmmunition,
+                CollisionLayer::Enemy,
+                exchange_damage,
+            )
+            .set_response(
+                CollisionLayer::Player,
+                CollisionLayer::EnemyAmmunition,
+                exchange_damage,
+            )
+            .set_response(CollisionLayer::Enemy, CollisionLayer::Enemy, no_damage)
+            .set_response(CollisionLayer::Player, CollisionLayer::Pickup, |_, _| {
+                // The pickup is used up, the player is unharmed
+                CollisionDamage {
+                    affected_damage: 0,
+                    other_damage: 1,
+                }
+            });
+
+        table
+    }
+}
+
+impl CollisionResponseTable {
+    /// Sets the response for collisions between the layers, replacing any existing response for the pair in either order
+    pub fn set_response(
+        &mut self,
+        first_layer: CollisionLayer,
+        second_layer: CollisionLayer,
+        response: CollisionResponse,
+    ) -> &mut Self {
+        self.responses.remove(&(second_layer, first_layer));
+        self.responses.insert((first_layer, second_layer), response);
+
+        self
+    }
+
+    /// Returns the response for the collision along with the drawables in the order the response expects them, None if the layers have no response
+    pub fn get_response<'a>(
+        &self,
+        first: &'a MinimalDrawableDetails,
+        second: &'a MinimalDrawableDetails,
+    ) -> Option<(
+        CollisionResponse,
+        &'a MinimalDrawableDetails,
+        &'a MinimalDrawableDetails,
+    )> {
+        if let Some(response) = self
+            .responses
+            .get(&(first.collision_layer, second.collision_layer))
+        {
+            return Some((*response, first, second));
+        }
+
+        self.responses
+            .get(&(second.collision_layer, first.collision_layer))
+            .map(|response| (*response, second, first))
+    }
+}
+
+/// The drawables touch without hurting each other
+fn no_damage(_: &MinimalDrawableDetails, _: &MinimalDrawableDetails) -> CollisionDamage {
+    CollisionDamage {
+        affected_damage: 0,
+        other_damage: 0,
+    }
+}
+
+/// Both drawables take the other's damage
+fn exchange_damage(
+    affected: &MinimalDrawableDetails,
+    other: &MinimalDrawableDetails,
+) -> CollisionDamage {
+    CollisionDamage {
+        affected_damage: other.drawable_type.get_damage(),
+        other_damage: affected.drawable_type.get_damage(),
+    }
+}
@@ -0,0 +1,212 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    api::display::Point,
+    components::{SteeringBehaviour, SteeringTarget},
+    entities::{Bullet, EnemyShip, MovementPattern, SeekerMine},
+};
+
+use super::EntityController;
+
+pub struct EnemyShipController {
+    pub entity_controller: EntityController<EnemyShip>,
+    /// Bullets fired by every enemy ship, these only collide with the player
+    pub bullet_entity_controller: EntityController<Bullet>,
+    pub mine_entity_controller: EntityController<SeekerMine>,
+    dimensions: Point<i64>,
+    // Spawn locations and movement patterns come from this so a game's enemies can be reproduced from its seed
+    rng: StdRng,
+}
+
+impl EnemyShipController {
+    /// Creates a new instance of the enemy ship controller
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - Dimensions of the world the ships fly through
+    /// * `seed` - Seed for the enemy ships, the same seed always spawns the same ships
+    pub fn new(dimensions: Point<i64>, seed: u64) -> Self {
+        Self {
+            entity_controller: EntityController::new(),
+            bullet_entity_controller: EntityController::new(),
+            mine_entity_controller: EntityController::new(),
+            dimensions,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Spawns an enemy ship with a random movement pattern at the top of the world, in the column or a random one if None
+    pub fn spawn_enemy_ship(&mut self, column: Option<i64>) -> &mut Self {
+        let location = Point::new(self.get_spawn_column(column), -1);
+        let pattern = MovementPattern::ALL[self.rng.gen_range(0..MovementPattern::ALL.len())];
+
+        self.entity_controller
+            .spawn_entity(EnemyShip::new(location, pattern));
+
+        self
+    }
+
+    /// Spawns a seeker mine at the top of the world, in the column or a random one if None
+    pub fn spawn_seeker_mine(&mut self, column: Option<i64>) -> &mut Self {
+        let column = self.get_spawn_column(column);
+        let mut mine = SeekerMine::new(Point::new(column, 0));
+
+        // Only the bottom row starts in the world, anything higher is deleted as outside the world before it's drawn
+        let height = mine.drawable.layout.dimensions.height;
+
+        mine.drawable
+            .teleport(Point::new(column as f64, (1 - height) as f64));
+
+        self.mine_entity_controller.spawn_entity(mine);
+
+        self
+    }
+
+    fn get_spawn_column(&mut self, column: Option<i64>) -> i64 {
+        column.unwrap_or_else(|| self.rng.gen_range(0..self.dimensions.width))
+    }
+
+    /// Moves the ships along their patterns, fires at the target and steers the mines
+    ///
+    /// # Arguments
+    ///
+    /// * `game_loop_duration` - How long the loop took in milliseconds
+    /// * `target` - What the ships fire at and the mines steer relative to, None holds their fire and leaves the mines drifting
+    pub fn handle_game_loop(
+        &mut self,
+        game_loop_duration: u128,
+        target: Option<SteeringTarget>,
+    ) -> &mut Self {
+        if let Some(target) = target {
+            for mine in self.mine_entity_controller.get_entities_mut() {
+                mine.steer(&target, game_loop_duration);
+            }
+        }
+
+        let mut bullets = vec![];
+
+        for enemy_ship in self.entity_controller.get_entities_mut() {
+            if enemy_ship.handle_game_loop(game_loop_duration) {
+                if let Some(target) = target {
+                    bullets.push(enemy_ship.fire_at(target.location.round()));
+                }
+            }
+        }
+
+        for bullet in bullets {
+            self.bullet_entity_controller.spawn_entity(bullet);
+        }
+
+        self
+    }
+
+    /// Makes the mines steer away from their target instead of towards it, used to give the player room while they can't fight back
+    pub fn set_mines_fleeing(&mut self, fleeing: bool) -> &mut Self {
+        let behaviour = if fleeing {
+            SteeringBehaviour::Flee
+        } else {
+            SteeringBehaviour::Seek
+        };
+
+        for mine in self.mine_entity_controller.get_entities_mut() {
+            mine.steering.behaviour = behaviour;
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::{DisplayController, Point},
+        components::SteeringTarget,
+        entities::ENEMY_SHIP_FIRE_RATE,
+    };
+
+    use super::EnemyShipController;
+
+    const TARGET: SteeringTarget = SteeringTarget {
+        location: Point::new(25.0, 25.0),
+        velocity: Point::new(0.0, 0.0),
+    };
+
+    #[test]
+    fn it_spawns_ships_that_fire_at_the_target() {
+        let mut controller = EnemyShipController::new(Point::new(50, 30), 1);
+
+        controller.spawn_enemy_ship(None).spawn_enemy_ship(None);
+
+        assert_eq!(
+            controller.entity_controller.get_all_drawable_states().len(),
+            2
+        );
+
+        // Without a target the ship holds its fire
+        controller.handle_game_loop(ENEMY_SHIP_FIRE_RATE, None);
+
+        assert!(controller
+            .bullet_entity_controller
+            .get_all_drawable_states()
+            .is_empty());
+
+        controller.handle_game_loop(ENEMY_SHIP_FIRE_RATE, Some(TARGET));
+
+        assert_eq!(
+            controller
+                .bullet_entity_controller
+                .get_all_drawable_states()
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn it_steers_mines_towards_the_target_unless_fleeing() {
+        let mut controller = EnemyShipController::new(Point::new(50, 30), 1);
+
+        controller.spawn_seeker_mine(None);
+
+        let get_mine_velocity = |controller: &EnemyShipController| {
+            controller.mine_entity_controller.get_all_drawable_states()[0].velocity
+        };
+
+        let target = SteeringTarget {
+            location: Point::new(0.0, 10.0),
+            velocity: Point::new(0.0, 0.0),
+        };
+
+        controller.handle_game_loop(1000, Some(target));
+
+        // Mines spawn at the top of the world, the target is below them
+        assert!(get_mine_velocity(&controller).height > 0.0);
+
+        controller.set_mines_fleeing(true);
+
+        for _ in 0..10 {
+            controller.handle_game_loop(500, Some(target));
+        }
+
+        assert!(get_mine_velocity(&controller).height < 0.0);
+    }
+
+    #[test]
+    fn it_keeps_new_mines_through_their_first_draw() {
+        let mut controller = EnemyShipController::new(Point::new(50, 30), 1);
+        let mut display_controller =
+            DisplayController::new(Point::new(50, 40), Point::new(0, 10), Point::new(50, 30))
+                .unwrap();
+
+        controller.spawn_seeker_mine(None);
+
+        display_controller.draw_entity_controller_items(&mut controller.mine_entity_controller);
+
+        assert_eq!(
+            controller
+                .mine_entity_controller
+                .get_all_drawable_states()
+                .len(),
+            1
+        );
+    }
+}
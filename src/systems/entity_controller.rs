@@ -16,13 +16,29 @@ impl<T: Drawable + Health> EntityController<T> {
     }
 
     pub fn has_entity(&self, uuid: Uuid) -> bool {
-        self.entity_hashmap.get(&uuid).is_some()
+        self.entity_hashmap.contains_key(&uuid)
+    }
+
+    pub fn get_entity(&self, uuid: Uuid) -> Option<&T> {
+        self.entity_hashmap.get(&uuid)
+    }
+
+    pub fn get_entity_mut(&mut self, uuid: Uuid) -> Option<&mut T> {
+        self.entity_hashmap.get_mut(&uuid)
+    }
+
+    pub fn get_entities(&self) -> impl Iterator<Item = &T> {
+        self.entity_hashmap.values()
+    }
+
+    pub fn get_entities_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.entity_hashmap.values_mut()
     }
 
     pub fn get_all_drawable_states(&self) -> Vec<&DrawableState> {
         self.entity_hashmap
-            .iter()
-            .map(|(_, entity)| entity.get_drawable_state())
+            .values()
+            .map(|entity| entity.get_drawable_state())
             .collect()
     }
 
@@ -60,8 +76,8 @@ impl<T: Drawable + Health> EntityController<T> {
     }
 
     pub fn update_entity_positions(&mut self, game_loop_duration: u128) -> &mut Self {
-        for (_, entity) in self.entity_hashmap.iter_mut() {
-            entity.update_position(None, game_loop_duration);
+        for entity in self.entity_hashmap.values_mut() {
+            entity.update_position(game_loop_duration);
         }
 
         self
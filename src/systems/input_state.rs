@@ -0,0 +1,195 @@
+use std::collections::HashMap;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind};
+
+use crate::{
+    api::display::Point,
+    helpers::{get_mouse_position, get_pressed_key},
+};
+
+use super::{Action, KeyMap};
+
+/// How long a key is held after its first press when the terminal doesn't report releases. This needs to cover the terminal's delay before it starts repeating the key
+pub const INITIAL_HOLD_TIMEOUT: u128 = 300;
+/// How long a key stays held after each repeat when the terminal doesn't report releases
+pub const REPEAT_HOLD_TIMEOUT: u128 = 100;
+
+/// Tracks which keys are currently held down and which actions they are bound to.
+///
+/// Terminals that support the keyboard enhancement protocol report release events, in which case a key is held until it is released. Other terminals only send repeated presses while a key is held, so the key is treated as released once no press has been seen for a timeout.
+pub struct InputState {
+    pub key_map: KeyMap,
+    // Key -> time remaining before the key is treated as released, only used when release events aren't supported
+    held_keys: HashMap<KeyCode, u128>,
+    supports_release_events: bool,
+    // Screen cell the mouse was last seen over, None until the first mouse event
+    mouse_position: Option<Point<i64>>,
+}
+
+impl InputState {
+    pub fn new(key_map: KeyMap) -> Self {
+        Self {
+            key_map,
+            held_keys: HashMap::new(),
+            supports_release_events: false,
+            mouse_position: None,
+        }
+    }
+
+    pub fn handle_event(&mut self, event: &Event) -> &mut Self {
+        if let Some(mouse_position) = get_mouse_position(event) {
+            self.mouse_position = Some(mouse_position);
+        }
+
+        if let Event::Key(KeyEvent { code, kind, .. }) = event {
+            match kind {
+                KeyEventKind::Release => {
+                    // Receiving a release proves the terminal supports them, so stop using timeouts
+                    self.supports_release_events = true;
+
+                    self.held_keys.remove(code);
+                }
+                KeyEventKind::Press | KeyEventKind::Repeat => {
+                    let timeout = if self.held_keys.contains_key(code) {
+                        REPEAT_HOLD_TIMEOUT
+                    } else {
+                        INITIAL_HOLD_TIMEOUT
+                    };
+
+                    self.held_keys.insert(*code, timeout);
+                }
+            }
+        }
+
+        self
+    }
+
+    /// Releases keys whose timeout has run out, this does nothing once the terminal has reported a release event
+    pub fn handle_game_loop(&mut self, game_loop_duration: u128) -> &mut Self {
+        if self.supports_release_events {
+            return self;
+        }
+
+        self.held_keys.retain(|_, time_remaining| {
+            *time_remaining = time_remaining.saturating_sub(game_loop_duration);
+
+            *time_remaining > 0
+        });
+
+        self
+    }
+
+    /// Treats every key as released, used when leaving the game for a menu so keys don't stay held
+    pub fn release_all(&mut self) -> &mut Self {
+        self.held_keys.clear();
+
+        self
+    }
+
+    /// Whether any key bound to the action is held
+    pub fn is_action_held(&self, action: Action) -> bool {
+        self.held_keys
+            .keys()
+            .any(|key| self.key_map.get_action(*key) == Some(action))
+    }
+
+    /// Returns the screen cell the mouse was last seen over
+    pub fn get_mouse_position(&self) -> Option<Point<i64>> {
+        self.mouse_position
+    }
+
+    /// Returns the action bound to the key if the event is a key press
+    pub fn get_pressed_action(&self, event: &Event) -> Option<Action> {
+        self.key_map.get_action(get_pressed_key(event)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, MouseButton, MouseEvent,
+        MouseEventKind,
+    };
+
+    use crate::{
+        api::display::Point,
+        systems::{Action, KeyMap},
+    };
+
+    use super::{InputState, INITIAL_HOLD_TIMEOUT, REPEAT_HOLD_TIMEOUT};
+
+    fn create_key_event(code: KeyCode, kind: KeyEventKind) -> Event {
+        Event::Key(KeyEvent::new_with_kind(code, KeyModifiers::NONE, kind))
+    }
+
+    #[test]
+    fn it_releases_keys_after_a_timeout_without_release_events() {
+        let mut input_state = InputState::new(KeyMap::default());
+
+        input_state.handle_event(&create_key_event(KeyCode::Up, KeyEventKind::Press));
+
+        input_state.handle_game_loop(INITIAL_HOLD_TIMEOUT - 1);
+
+        assert!(input_state.is_action_held(Action::MoveUp));
+
+        // A repeated press keeps the key held for the shorter repeat timeout
+        input_state.handle_event(&create_key_event(KeyCode::Up, KeyEventKind::Press));
+        input_state.handle_game_loop(REPEAT_HOLD_TIMEOUT - 1);
+
+        assert!(input_state.is_action_held(Action::MoveUp));
+
+        input_state.handle_game_loop(1);
+
+        assert!(!input_state.is_action_held(Action::MoveUp));
+    }
+
+    #[test]
+    fn it_holds_keys_until_released_when_release_events_are_supported() {
+        let mut input_state = InputState::new(KeyMap::default());
+
+        input_state.handle_event(&create_key_event(KeyCode::Left, KeyEventKind::Release));
+        input_state.handle_event(&create_key_event(KeyCode::Up, KeyEventKind::Press));
+        input_state.handle_event(&create_key_event(KeyCode::Left, KeyEventKind::Press));
+
+        input_state.handle_game_loop(INITIAL_HOLD_TIMEOUT * 10);
+
+        assert!(input_state.is_action_held(Action::MoveUp));
+        assert!(input_state.is_action_held(Action::MoveLeft));
+
+        input_state.handle_event(&create_key_event(KeyCode::Up, KeyEventKind::Release));
+
+        assert!(!input_state.is_action_held(Action::MoveUp));
+        assert!(input_state.is_action_held(Action::MoveLeft));
+    }
+
+    #[test]
+    fn it_holds_an_action_while_any_bound_key_is_held() {
+        let mut input_state = InputState::new(KeyMap::default());
+
+        input_state.handle_event(&create_key_event(KeyCode::Char('w'), KeyEventKind::Press));
+
+        assert!(input_state.is_action_held(Action::MoveUp));
+        assert!(!input_state.is_action_held(Action::MoveDown));
+    }
+
+    #[test]
+    fn it_tracks_the_mouse_position() {
+        let mut input_state = InputState::new(KeyMap::default());
+
+        assert_eq!(input_state.get_mouse_position(), None);
+
+        for kind in [
+            MouseEventKind::Moved,
+            MouseEventKind::Down(MouseButton::Left),
+        ] {
+            input_state.handle_event(&Event::Mouse(MouseEvent {
+                kind,
+                column: 12,
+                row: 7,
+                modifiers: KeyModifiers::NONE,
+            }));
+        }
+
+        assert_eq!(input_state.get_mouse_position(), Some(Point::new(12, 7)));
+    }
+}
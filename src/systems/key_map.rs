@@ -0,0 +1,447 @@
+use std::{collections::HashMap, fmt, fs, io};
+
+use crossterm::event::KeyCode;
+
+/// Everything the player can do with a key, keys are bound to actions through a KeyMap rather than being checked directly
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Fire,
+    FireSpread,
+    FireMissile,
+    SelectBasicWeapon,
+    SelectSpreadWeapon,
+    SelectLaser,
+    SelectMissiles,
+    Pause,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 13] = [
+        Action::MoveUp,
+        Action::MoveDown,
+        Action::MoveLeft,
+        Action::MoveRight,
+        Action::Fire,
+        Action::FireSpread,
+        Action::FireMissile,
+        Action::SelectBasicWeapon,
+        Action::SelectSpreadWeapon,
+        Action::SelectLaser,
+        Action::SelectMissiles,
+        Action::Pause,
+        Action::Quit,
+    ];
+
+    /// The name used for the action in the controls config file
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::Fire => "fire",
+            Action::FireSpread => "fire_spread",
+            Action::FireMissile => "fire_missile",
+            Action::SelectBasicWeapon => "select_basic_weapon",
+            Action::SelectSpreadWeapon => "select_spread_weapon",
+            Action::SelectLaser => "select_laser",
+            Action::SelectMissiles => "select_missiles",
+            Action::Pause => "pause",
+            Action::Quit => "quit",
+        }
+    }
+
+    /// The name shown to the user in menus
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Action::MoveUp => "Move up",
+            Action::MoveDown => "Move down",
+            Action::MoveLeft => "Move left",
+            Action::MoveRight => "Move right",
+            Action::Fire => "Fire",
+            Action::FireSpread => "Fire spread",
+            Action::FireMissile => "Fire missile",
+            Action::SelectBasicWeapon => "Basic weapon",
+            Action::SelectSpreadWeapon => "Spread weapon",
+            Action::SelectLaser => "Laser",
+            Action::SelectMissiles => "Missiles",
+            Action::Pause => "Pause",
+            Action::Quit => "Quit",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Action> {
+        Action::ALL
+            .into_iter()
+            .find(|action| action.get_name() == name)
+    }
+}
+
+#[derive(Debug)]
+pub enum KeyMapError {
+    /// Line number and the line that couldn't be split into an action and keys
+    InvalidLine(usize, String),
+    /// Line number and the unknown action name
+    UnknownAction(usize, String),
+    /// Line number and the unknown key name
+    UnknownKey(usize, String),
+    IoError(io::ErrorKind),
+}
+
+impl fmt::Display for KeyMapError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidLine(line_number, line) => write!(
+                formatter,
+                "line {}: expected `action = keys` but found `{}`",
+                line_number, line
+            ),
+            Self::UnknownAction(line_number, name) => {
+                write!(formatter, "line {}: unknown action `{}`", line_number, name)
+            }
+            Self::UnknownKey(line_number, name) => {
+                write!(formatter, "line {}: unknown key `{}`", line_number, name)
+            }
+            Self::IoError(kind) => write!(formatter, "file error: {:?}", kind),
+        }
+    }
+}
+
+impl From<io::Error> for KeyMapError {
+    fn from(error: io::Error) -> Self {
+        Self::IoError(error.kind())
+    }
+}
+
+/// Maps keys to the actions they trigger, a single action can have several keys but each key only triggers one action
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyMap {
+    bindings: HashMap<KeyCode, Action>,
+}
+
+impl Default for KeyMap {
+    /// Arrow keys and WASD both move, so the game is playable on keyboards without comfortable arrow keys
+    fn default() -> Self {
+        let mut key_map = Self {
+            bindings: HashMap::new(),
+        };
+
+        key_map
+            .bind(KeyCode::Up, Action::MoveUp)
+            .bind(KeyCode::Char('w'), Action::MoveUp)
+            .bind(KeyCode::Down, Action::MoveDown)
+            .bind(KeyCode::Char('s'), Action::MoveDown)
+            .bind(KeyCode::Left, Action::MoveLeft)
+            .bind(KeyCode::Char('a'), Action::MoveLeft)
+            .bind(KeyCode::Right, Action::MoveRight)
+            .bind(KeyCode::Char('d'), Action::MoveRight)
+            .bind(KeyCode::Char(' '), Action::Fire)
+            .bind(KeyCode::Enter, Action::FireSpread)
+            .bind(KeyCode::Char('f'), Action::FireMissile)
+            .bind(KeyCode::Char('1'), Action::SelectBasicWeapon)
+            .bind(KeyCode::Char('2'), Action::SelectSpreadWeapon)
+            .bind(KeyCode::Char('3'), Action::SelectLaser)
+            .bind(KeyCode::Char('4'), Action::SelectMissiles)
+            .bind(KeyCode::Char('p'), Action::Pause)
+            .bind(KeyCode::Esc, Action::Quit);
+
+        key_map
+    }
+}
+
+impl KeyMap {
+    /// Loads the key map from a controls config file, starting from the default bindings. A missing file gives the default bindings
+    pub fn load(path: &str) -> Result<Self, KeyMapError> {
+        match fs::read_to_string(path) {
+            Ok(config) => Self::from_config(&config),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), KeyMapError> {
+        fs::write(path, self.to_config())?;
+
+        Ok(())
+    }
+
+    /// Parses a controls config, each line has the form `action = key, key` and a comma key is written as `comma`. Every action listed replaces the default keys for that action, blank lines and lines starting with # are ignored
+    pub fn from_config(config: &str) -> Result<Self, KeyMapError> {
+        let mut key_map = Self::default();
+
+        for (index, line) in config.lines().enumerate() {
+            let line_number = index + 1;
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (action_name, keys) = line
+                .split_once('=')
+                .ok_or_else(|| KeyMapError::InvalidLine(line_number, line.to_string()))?;
+
+            let action_name = action_name.trim();
+
+            let action = Action::from_name(action_name)
+                .ok_or_else(|| KeyMapError::UnknownAction(line_number, action_name.to_string()))?;
+
+            // Rebinding an action's only key to another action leaves it without keys, which is saved as nothing after the =
+            let keys = if keys.trim().is_empty() {
+                Vec::new()
+            } else {
+                keys.split(',')
+                    .map(|key| {
+                        parse_key(key).ok_or_else(|| {
+                            KeyMapError::UnknownKey(line_number, key.trim().to_string())
+                        })
+                    })
+                    .collect::<Result<Vec<KeyCode>, KeyMapError>>()?
+            };
+
+            key_map.unbind_action(action);
+
+            for key in keys {
+                key_map.bind(key, action);
+            }
+        }
+
+        Ok(key_map)
+    }
+
+    pub fn to_config(&self) -> String {
+        Action::ALL
+            .iter()
+            .map(|action| format!("{} = {}\n", action.get_name(), self.get_keys_label(*action)))
+            .collect()
+    }
+
+    /// Binds the key to the action, replacing anything the key was previously bound to
+    pub fn bind(&mut self, key: KeyCode, action: Action) -> &mut Self {
+        self.bindings.insert(normalize_key(key), action);
+
+        self
+    }
+
+    /// Replaces all of the keys for the action with the provided key
+    pub fn rebind(&mut self, action: Action, key: KeyCode) -> &mut Self {
+        self.unbind_action(action).bind(key, action)
+    }
+
+    fn unbind_action(&mut self, action: Action) -> &mut Self {
+        self.bindings
+            .retain(|_, bound_action| *bound_action != action);
+
+        self
+    }
+
+    pub fn get_action(&self, key: KeyCode) -> Option<Action> {
+        self.bindings.get(&normalize_key(key)).copied()
+    }
+
+    /// Returns the keys bound to the action, sorted by name so they are displayed consistently
+    pub fn get_keys(&self, action: Action) -> Vec<KeyCode> {
+        let mut keys: Vec<KeyCode> = self
+            .bindings
+            .iter()
+            .filter(|(_, bound_action)| **bound_action == action)
+            .map(|(key, _)| *key)
+            .collect();
+
+        keys.sort_by_key(|key| get_key_name(*key));
+
+        keys
+    }
+
+    /// Returns the names of all the keys bound to the action separated by commas
+    pub fn get_keys_label(&self, action: Action) -> String {
+        self.get_keys(action)
+            .into_iter()
+            .map(get_key_name)
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+}
+
+/// Letters are bound in lower case so holding shift doesn't change the action
+fn normalize_key(key: KeyCode) -> KeyCode {
+    match key {
+        KeyCode::Char(character) => KeyCode::Char(character.to_ascii_lowercase()),
+        _ => key,
+    }
+}
+
+/// Keys that are written by name in the controls config. Comma separates keys in the config so it needs a name too
+const KEY_NAMES: [(KeyCode, &str); 23] = [
+    (KeyCode::Up, "up"),
+    (KeyCode::Down, "down"),
+    (KeyCode::Left, "left"),
+    (KeyCode::Right, "right"),
+    (KeyCode::Home, "home"),
+    (KeyCode::End, "end"),
+    (KeyCode::PageUp, "pageup"),
+    (KeyCode::PageDown, "pagedown"),
+    (KeyCode::Insert, "insert"),
+    (KeyCode::Delete, "delete"),
+    (KeyCode::Char(' '), "space"),
+    (KeyCode::Char(','), "comma"),
+    (KeyCode::Enter, "enter"),
+    (KeyCode::Esc, "esc"),
+    (KeyCode::Tab, "tab"),
+    (KeyCode::BackTab, "backtab"),
+    (KeyCode::Backspace, "backspace"),
+    (KeyCode::CapsLock, "capslock"),
+    (KeyCode::ScrollLock, "scrolllock"),
+    (KeyCode::NumLock, "numlock"),
+    (KeyCode::PrintScreen, "printscreen"),
+    (KeyCode::Pause, "pause"),
+    (KeyCode::Menu, "menu"),
+];
+
+/// Parses a key name from the controls config, this is the reverse of get_key_name
+pub fn parse_key(name: &str) -> Option<KeyCode> {
+    let name = name.trim().to_ascii_lowercase();
+
+    if let Some((key, _)) = KEY_NAMES.iter().find(|(_, key_name)| *key_name == name) {
+        return Some(*key);
+    }
+
+    let mut characters = name.chars();
+
+    match (characters.next(), characters.next()) {
+        (Some(character), None) => Some(KeyCode::Char(character)),
+        (Some('f'), Some(_)) => Some(KeyCode::F(name[1..].parse().ok()?)),
+        _ => None,
+    }
+}
+
+/// Returns the name of the key used in the controls config and menus
+pub fn get_key_name(key: KeyCode) -> String {
+    if let Some((_, name)) = KEY_NAMES.iter().find(|(named_key, _)| *named_key == key) {
+        return name.to_string();
+    }
+
+    match key {
+        KeyCode::Char(character) => character.to_string(),
+        KeyCode::F(number) => format!("f{}", number),
+        other => format!("{:?}", other).to_lowercase(),
+    }
+}
+
+/// Whether the key can be bound, only keys whose name parses back to the same key can be saved to the controls config
+pub fn is_bindable(key: KeyCode) -> bool {
+    parse_key(&get_key_name(key)) == Some(normalize_key(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::KeyCode;
+
+    use super::{get_key_name, is_bindable, parse_key, Action, KeyMap, KeyMapError};
+
+    #[test]
+    fn it_binds_arrows_and_wasd_by_default() {
+        let key_map = KeyMap::default();
+
+        assert_eq!(key_map.get_action(KeyCode::Up), Some(Action::MoveUp));
+        assert_eq!(key_map.get_action(KeyCode::Char('w')), Some(Action::MoveUp));
+        // Shift shouldn't change the action
+        assert_eq!(
+            key_map.get_action(KeyCode::Char('A')),
+            Some(Action::MoveLeft)
+        );
+        assert_eq!(key_map.get_action(KeyCode::Char(' ')), Some(Action::Fire));
+    }
+
+    #[test]
+    fn it_overrides_actions_from_a_config() {
+        let key_map = KeyMap::from_config(
+            "# Left handed controls
+fire = f, j
+move_up = i",
+        )
+        .unwrap();
+
+        assert_eq!(key_map.get_action(KeyCode::Char('f')), Some(Action::Fire));
+        assert_eq!(key_map.get_action(KeyCode::Char('j')), Some(Action::Fire));
+        assert_eq!(key_map.get_action(KeyCode::Char(' ')), None);
+        assert_eq!(key_map.get_keys(Action::MoveUp), vec![KeyCode::Char('i')]);
+        // Actions that aren't listed keep their defaults
+        assert_eq!(key_map.get_action(KeyCode::Down), Some(Action::MoveDown));
+    }
+
+    #[test]
+    fn it_reports_the_line_of_config_errors() {
+        let result = KeyMap::from_config("fire = space\n\njump = up");
+
+        assert!(matches!(result, Err(KeyMapError::UnknownAction(3, name)) if name == "jump"));
+
+        let result = KeyMap::from_config("fire = spacebar");
+
+        assert!(matches!(result, Err(KeyMapError::UnknownKey(1, name)) if name == "spacebar"));
+    }
+
+    #[test]
+    fn it_round_trips_through_a_config() {
+        let mut key_map = KeyMap::default();
+
+        key_map.rebind(Action::Pause, KeyCode::F(1));
+
+        assert_eq!(KeyMap::from_config(&key_map.to_config()).unwrap(), key_map);
+    }
+
+    #[test]
+    fn it_round_trips_every_bindable_key_through_its_name() {
+        let mut keys = vec![
+            KeyCode::Backspace,
+            KeyCode::Enter,
+            KeyCode::Left,
+            KeyCode::Right,
+            KeyCode::Up,
+            KeyCode::Down,
+            KeyCode::Home,
+            KeyCode::End,
+            KeyCode::PageUp,
+            KeyCode::PageDown,
+            KeyCode::Tab,
+            KeyCode::BackTab,
+            KeyCode::Delete,
+            KeyCode::Insert,
+            KeyCode::Esc,
+            KeyCode::CapsLock,
+            KeyCode::ScrollLock,
+            KeyCode::NumLock,
+            KeyCode::PrintScreen,
+            KeyCode::Pause,
+            KeyCode::Menu,
+        ];
+
+        keys.extend((1..=24).map(KeyCode::F));
+        keys.extend(
+            (' '..='~')
+                .filter(|character| !character.is_ascii_uppercase())
+                .map(KeyCode::Char),
+        );
+
+        for key in keys {
+            assert!(is_bindable(key), "{:?} should be bindable", key);
+            assert_eq!(parse_key(&get_key_name(key)), Some(key));
+
+            // Every bindable key survives being saved and loaded
+            let mut key_map = KeyMap::default();
+
+            key_map.rebind(Action::Fire, key);
+
+            assert_eq!(KeyMap::from_config(&key_map.to_config()).unwrap(), key_map);
+        }
+
+        // Keys without a name in the config can't be bound
+        assert!(!is_bindable(KeyCode::Null));
+        assert!(!is_bindable(KeyCode::KeypadBegin));
+    }
+}
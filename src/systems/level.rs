@@ -0,0 +1,300 @@
+use std::{fmt, fs, io, str::FromStr};
+
+use crate::api::display::Point;
+
+use super::{BossTrigger, VelocityRange, WaveClearCondition, WaveDefinition};
+
+#[derive(Debug)]
+pub enum LevelError {
+    /// Line number, column number and a description of the problem, both numbers start from 1
+    ParseError(usize, usize, String),
+    IoError(io::ErrorKind),
+}
+
+impl From<io::Error> for LevelError {
+    fn from(error: io::Error) -> Self {
+        Self::IoError(error.kind())
+    }
+}
+
+impl fmt::Display for LevelError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::ParseError(line, column, message) => {
+                write!(formatter, "line {}, column {}: {}", line, column, message)
+            }
+            Self::IoError(kind) => write!(formatter, "couldn't read the level file: {:?}", kind),
+        }
+    }
+}
+
+/// Loads the waves from a level file, see parse_level for the format
+pub fn load_level(path: &str) -> Result<Vec<WaveDefinition>, LevelError> {
+    parse_level(&fs::read_to_string(path)?)
+}
+
+/// Parses a level, each wave starts with a `[wave]` line followed by `key = value` lines. Anything after a # is a comment and blank lines are ignored
+///
+/// * `asteroids`, `enemy_ships`, `seeker_mines` - How many of each enemy the wave spawns
+/// * `boss` - `none`, `start` to spawn a boss before anything else or `end` to spawn it after everything else
+/// * `intermission` - Milliseconds the wave's banner is shown for before it starts
+/// * `interval` - Milliseconds between spawns
+/// * `lanes` - Comma separated columns to spawn in, as fractions of the world's width from 0 to 1
+/// * `velocity` - Asteroid velocity ranges across and down in cells per second, for example `-10..10, 5..20`
+/// * `clear` - `field` to end the wave once every enemy is gone or `survive 30000` to end it after that many milliseconds
+///
+/// Keys that aren't listed keep the values from WaveDefinition::default
+pub fn parse_level(level: &str) -> Result<Vec<WaveDefinition>, LevelError> {
+    let mut waves: Vec<WaveDefinition> = vec![];
+
+    for (index, line) in level.lines().enumerate() {
+        let line_number = index + 1;
+        let content = line.split('#').next().unwrap_or_default();
+        let trimmed = content.trim();
+
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let error = |token: &str, message: String| {
+            LevelError::ParseError(line_number, get_column(line, token), message)
+        };
+
+        if let Some(section) = trimmed
+            .strip_prefix('[')
+            .and_then(|section| section.strip_suffix(']'))
+        {
+            if section.trim() != "wave" {
+                return Err(error(
+                    trimmed,
+                    format!("unknown section `{}`, expected `[wave]`", section.trim()),
+                ));
+            }
+
+            waves.push(WaveDefinition::default());
+
+            continue;
+        }
+
+        let (key, value) = trimmed.split_once('=').ok_or_else(|| {
+            error(
+                trimmed,
+                "expected `key = value` or a `[wave]` section".to_string(),
+            )
+        })?;
+
+        let key = key.trim();
+        let value = value.trim();
+
+        let wave = waves
+            .last_mut()
+            .ok_or_else(|| error(key, format!("`{}` must be inside a `[wave]` section", key)))?;
+
+        match key {
+            "asteroids" => wave.asteroid_count = parse_number(value, &error)?,
+            "enemy_ships" => wave.enemy_ship_count = parse_number(value, &error)?,
+            "seeker_mines" => wave.seeker_mine_count = parse_number(value, &error)?,
+            "intermission" => wave.intermission_duration = parse_number(value, &error)?,
+            "interval" => wave.spawn_interval = parse_number(value, &error)?,
+            "boss" => {
+                wave.boss = match value {
+                    "none" => None,
+                    "start" => Some(BossTrigger::WaveStart),
+                    "end" => Some(BossTrigger::WaveEnd),
+                    _ => {
+                        return Err(error(
+                            value,
+                            format!(
+                                "unknown boss trigger `{}`, expected `none`, `start` or `end`",
+                                value
+                            ),
+                        ))
+                    }
+                }
+            }
+            "lanes" => {
+                wave.spawn_lanes = value
+                    .split(',')
+                    .map(|lane| {
+                        let lane = lane.trim();
+                        let fraction = parse_finite_number(lane, &error)?;
+
+                        if !(0.0..=1.0).contains(&fraction) {
+                            return Err(error(
+                                lane,
+                                format!("lane `{}` must be between 0 and 1", lane),
+                            ));
+                        }
+
+                        Ok(fraction)
+                    })
+                    .collect::<Result<Vec<f64>, LevelError>>()?
+            }
+            "velocity" => {
+                let (width, height) = value.split_once(',').ok_or_else(|| {
+                    error(
+                        value,
+                        "expected ranges across and down, for example `-10..10, 5..20`".to_string(),
+                    )
+                })?;
+
+                let (min_width, max_width) = parse_range(width.trim(), &error)?;
+                let (min_height, max_height) = parse_range(height.trim(), &error)?;
+
+                wave.asteroid_velocity = VelocityRange::new(
+                    Point::new(min_width, min_height),
+                    Point::new(max_width, max_height),
+                );
+            }
+            "clear" => {
+                let mut words = value.split_whitespace();
+
+                wave.clear_condition = match (words.next(), words.next()) {
+                    (Some("field"), None) => WaveClearCondition::ClearField,
+                    (Some("survive"), Some(duration)) => {
+                        WaveClearCondition::Survive(parse_number(duration, &error)?)
+                    }
+                    _ => {
+                        return Err(error(
+                            value,
+                            format!(
+                                "unknown clear condition `{}`, expected `field` or `survive <milliseconds>`",
+                                value
+                            ),
+                        ))
+                    }
+                };
+            }
+            _ => return Err(error(key, format!("unknown key `{}`", key))),
+        }
+    }
+
+    if waves.is_empty() {
+        return Err(LevelError::ParseError(
+            level.lines().count().max(1),
+            1,
+            "the level has no `[wave]` sections".to_string(),
+        ));
+    }
+
+    Ok(waves)
+}
+
+/// Returns the column the token starts at within the line, the token must be a slice of the line
+fn get_column(line: &str, token: &str) -> usize {
+    token.as_ptr() as usize - line.as_ptr() as usize + 1
+}
+
+fn parse_number<T: FromStr>(
+    token: &str,
+    error: &impl Fn(&str, String) -> LevelError,
+) -> Result<T, LevelError> {
+    token
+        .parse()
+        .map_err(|_| error(token, format!("`{}` isn't a valid number", token)))
+}
+
+/// Parses a number that has to be finite, `inf` and `NaN` would otherwise be accepted as floats
+fn parse_finite_number(
+    token: &str,
+    error: &impl Fn(&str, String) -> LevelError,
+) -> Result<f64, LevelError> {
+    let number: f64 = parse_number(token, error)?;
+
+    if !number.is_finite() {
+        return Err(error(token, format!("`{}` must be a finite number", token)));
+    }
+
+    Ok(number)
+}
+
+/// Parses a range of the form `min..max`
+fn parse_range(
+    token: &str,
+    error: &impl Fn(&str, String) -> LevelError,
+) -> Result<(f64, f64), LevelError> {
+    let (min, max) = token.split_once("..").ok_or_else(|| {
+        error(
+            token,
+            format!("`{}` isn't a range, expected `min..max`", token),
+        )
+    })?;
+
+    let min = parse_finite_number(min.trim(), error)?;
+    let max = parse_finite_number(max.trim(), error)?;
+
+    if min > max {
+        return Err(error(
+            token,
+            format!("the range `{}` has its minimum above its maximum", token),
+        ));
+    }
+
+    Ok((min, max))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        systems::{BossTrigger, WaveClearCondition, WaveDefinition},
+    };
+
+    use super::{parse_level, LevelError};
+
+    const EXAMPLE_LEVEL: &str = include_str!("../../levels/example.level");
+
+    #[test]
+    fn it_parses_waves() {
+        let waves = parse_level(
+            "# Opening
+[wave]
+asteroids = 4   # Slow to start
+velocity = -5..5, 2..8
+
+[wave]
+enemy_ships = 2
+boss = end
+lanes = 0.25, 0.75
+clear = survive 20000",
+        )
+        .unwrap();
+
+        assert_eq!(waves.len(), 2);
+        assert_eq!(waves[0].asteroid_count, 4);
+        assert_eq!(waves[0].asteroid_velocity.min, Point::new(-5.0, 2.0));
+        assert_eq!(waves[0].asteroid_velocity.max, Point::new(5.0, 8.0));
+        // Keys that aren't listed keep their defaults
+        assert_eq!(
+            waves[0].spawn_interval,
+            WaveDefinition::default().spawn_interval
+        );
+        assert_eq!(waves[1].boss, Some(BossTrigger::WaveEnd));
+        assert_eq!(waves[1].spawn_lanes, vec![0.25, 0.75]);
+        assert_eq!(waves[1].clear_condition, WaveClearCondition::Survive(20000));
+    }
+
+    #[test]
+    fn it_reports_the_line_and_column_of_errors() {
+        let error = |level: &str| match parse_level(level) {
+            Err(LevelError::ParseError(line, column, _)) => (line, column),
+            result => panic!("expected a parse error, got {:?}", result),
+        };
+
+        assert_eq!(error("asteroids = 3"), (1, 1));
+        assert_eq!(error("[wave]\n\nasteroids = lots"), (3, 13));
+        assert_eq!(error("[wave]\nvelocity = -5..5, 8..2"), (2, 19));
+        assert_eq!(error("[wave]\n  lanes = 0.5, 2"), (2, 16));
+        assert_eq!(error("[wave]\nvelocity = -inf..5, 2..8"), (2, 12));
+        assert_eq!(error("[wave]\nvelocity = -5..5, NaN..8"), (2, 19));
+        assert_eq!(error("[wave]\nlanes = 0.5, nan"), (2, 14));
+        assert_eq!(error("[wave]\nspeed = 3"), (2, 1));
+        assert_eq!(error("[waves]"), (1, 1));
+        assert_eq!(error("# Nothing here"), (1, 1));
+    }
+
+    #[test]
+    fn it_parses_the_example_level() {
+        assert!(!parse_level(EXAMPLE_LEVEL).unwrap().is_empty());
+    }
+}
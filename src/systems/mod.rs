@@ -1,7 +1,35 @@
 mod asteroid_controller;
+mod boss_controller;
+mod bounce;
 mod collision_detection;
+mod collision_responses;
+mod enemy_ship_controller;
 mod entity_controller;
+mod input_state;
+mod key_map;
+mod level;
+mod pickup_controller;
+mod safe_spawn;
+mod screen_effects;
+mod ship_physics;
+mod sparks;
+mod starfield;
+mod wave_director;
 
 pub use asteroid_controller::*;
+pub use boss_controller::*;
+pub use bounce::*;
 pub use collision_detection::*;
+pub use collision_responses::*;
+pub use enemy_ship_controller::*;
 pub use entity_controller::*;
+pub use input_state::*;
+pub use key_map::*;
+pub use level::*;
+pub use pickup_controller::*;
+pub use safe_spawn::*;
+pub use screen_effects::*;
+pub use ship_physics::*;
+pub use sparks::*;
+pub use starfield::*;
+pub use wave_director::*;
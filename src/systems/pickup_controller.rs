@@ -0,0 +1,74 @@
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    api::display::Point,
+    entities::{Pickup, PickupKind},
+};
+
+use super::EntityController;
+
+pub struct PickupController {
+    pub entity_controller: EntityController<Pickup>,
+    /// Chance from 0 to 1 of a destroyed enemy dropping a pickup
+    pub drop_chance: f64,
+    // Drops and their kinds come from this so a game's pickups can be reproduced from its seed
+    rng: StdRng,
+}
+
+impl PickupController {
+    /// Creates a new instance of the pickup controller
+    ///
+    /// # Arguments
+    ///
+    /// * `drop_chance` - Chance from 0 to 1 of a destroyed enemy dropping a pickup
+    /// * `seed` - Seed for the pickups, the same seed always drops the same pickups
+    pub fn new(drop_chance: f64, seed: u64) -> Self {
+        Self {
+            entity_controller: EntityController::new(),
+            drop_chance,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Drops a random pickup at the location
+    pub fn drop_pickup(&mut self, location: Point<f64>) -> &mut Self {
+        let kind = PickupKind::ALL[self.rng.gen_range(0..PickupKind::ALL.len())];
+
+        self.entity_controller
+            .spawn_entity(Pickup::new(kind, location));
+
+        self
+    }
+
+    /// Called when an enemy is destroyed at the location, it drops a pickup at the drop chance
+    pub fn handle_enemy_destroyed(&mut self, location: Point<f64>) -> &mut Self {
+        if self.rng.gen_bool(self.drop_chance.clamp(0.0, 1.0)) {
+            self.drop_pickup(location);
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::display::Point;
+
+    use super::PickupController;
+
+    #[test]
+    fn it_drops_pickups_at_the_drop_chance() {
+        let location = Point::new(10.0, 10.0);
+
+        let mut never = PickupController::new(0.0, 1);
+        let mut always = PickupController::new(1.0, 1);
+
+        for _ in 0..10 {
+            never.handle_enemy_destroyed(location);
+            always.handle_enemy_destroyed(location);
+        }
+
+        assert!(never.entity_controller.get_all_drawable_states().is_empty());
+        assert_eq!(always.entity_controller.get_all_drawable_states().len(), 10);
+    }
+}
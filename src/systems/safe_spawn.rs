@@ -0,0 +1,124 @@
+use crate::{api::display::Point, components::DrawableState};
+
+/// Amount of empty cells required between a spawned drawable and any obstacle
+pub const SAFE_SPAWN_MARGIN: i64 = 3;
+
+/// Checks whether the bounding boxes of a drawable at the location and the obstacle overlap, after growing the obstacle by the margin
+fn get_is_overlapping_obstacle(
+    location: &Point<i64>,
+    dimensions: &Point<i64>,
+    obstacle: &DrawableState,
+    margin: i64,
+) -> bool {
+    let obstacle_location = obstacle.get_location();
+    let obstacle_start = obstacle_location.sub_width(margin).sub_height(margin);
+    let obstacle_end = obstacle_location + obstacle.layout.dimensions + Point::new(margin, margin);
+
+    location.width < obstacle_end.width
+        && location.width + dimensions.width > obstacle_start.width
+        && location.height < obstacle_end.height
+        && location.height + dimensions.height > obstacle_start.height
+}
+
+/// Finds a location for a drawable that is clear of all the obstacles. The preferred location is tried first, then locations on the same row moving outwards, then the rows above it.
+///
+/// # Arguments
+///
+/// * `preferred_location` - Where the drawable would ideally spawn
+/// * `dimensions` - Dimensions of the drawable being spawned
+/// * `world_dimensions` - The spawned drawable is kept entirely within these dimensions
+/// * `obstacles` - Drawables that must not be near the spawn location
+///
+/// # Returns
+///
+/// * The safe location, or the preferred location if nowhere is clear
+pub fn get_safe_spawn_location(
+    preferred_location: Point<i64>,
+    dimensions: &Point<i64>,
+    world_dimensions: &Point<i64>,
+    obstacles: &[&DrawableState],
+) -> Point<i64> {
+    let max_location = *world_dimensions - *dimensions;
+
+    let is_safe = |location: &Point<i64>| {
+        !obstacles.iter().any(|obstacle| {
+            get_is_overlapping_obstacle(location, dimensions, obstacle, SAFE_SPAWN_MARGIN)
+        })
+    };
+
+    for height in (0..=preferred_location.height.min(max_location.height)).rev() {
+        for distance in 0..=max_location.width {
+            for width in [
+                preferred_location.width - distance,
+                preferred_location.width + distance,
+            ] {
+                if width < 0 || width > max_location.width {
+                    continue;
+                }
+
+                let location = Point::new(width, height);
+
+                if is_safe(&location) {
+                    return location;
+                }
+            }
+        }
+    }
+
+    preferred_location
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        api::display::Point,
+        components::Drawable,
+        entities::{Asteroid, AsteroidSize, Player},
+    };
+
+    use super::{get_safe_spawn_location, SAFE_SPAWN_MARGIN};
+
+    const WORLD_DIMENSIONS: Point<i64> = Point::new(60, 40);
+
+    #[test]
+    fn it_uses_the_preferred_location_when_it_is_clear() {
+        let player = Player::new(None);
+        let asteroid = Asteroid::new(AsteroidSize::Medium, Point::new(50, 5), Point::default());
+
+        let location = get_safe_spawn_location(
+            Point::new(20, 30),
+            &player.drawable.layout.dimensions,
+            &WORLD_DIMENSIONS,
+            &[asteroid.get_drawable_state()],
+        );
+
+        assert_eq!(location, Point::new(20, 30));
+    }
+
+    #[test]
+    fn it_moves_away_from_obstacles() {
+        let player = Player::new(None);
+        let dimensions = player.drawable.layout.dimensions;
+        let asteroid = Asteroid::new(AsteroidSize::Medium, Point::new(22, 32), Point::default());
+
+        let location = get_safe_spawn_location(
+            Point::new(20, 30),
+            &dimensions,
+            &WORLD_DIMENSIONS,
+            &[asteroid.get_drawable_state()],
+        );
+
+        assert_ne!(location, Point::new(20, 30));
+
+        let asteroid_location = asteroid.drawable.get_location();
+        let asteroid_end = asteroid_location + asteroid.drawable.layout.dimensions;
+
+        // Either fully to the left, right or above the asteroid including the margin
+        assert!(
+            location.width + dimensions.width + SAFE_SPAWN_MARGIN <= asteroid_location.width
+                || location.width >= asteroid_end.width + SAFE_SPAWN_MARGIN
+                || location.height + dimensions.height + SAFE_SPAWN_MARGIN
+                    <= asteroid_location.height
+        );
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use crossterm::style::Color;
+use uuid::Uuid;
+
+use crate::api::display::element::color_to_rgb;
+
+/// Tracks how long an effect has left to run
+#[derive(Debug, Clone, Copy)]
+struct EffectTimer {
+    duration: u128,
+    time_remaining: u128,
+}
+
+impl EffectTimer {
+    fn new(duration: u128) -> Self {
+        Self {
+            duration,
+            time_remaining: duration,
+        }
+    }
+
+    /// Returns false once the timer has run out
+    fn update(&mut self, game_loop_duration: u128) -> bool {
+        self.time_remaining = self.time_remaining.saturating_sub(game_loop_duration);
+
+        self.time_remaining > 0
+    }
+
+    fn get_time_elapsed(&self) -> u128 {
+        self.duration - self.time_remaining
+    }
+
+    /// Scales the provided value by how much of the timer is left, so effects fade out as they finish
+    fn decay(&self, value: i64) -> i64 {
+        if self.duration == 0 {
+            return 0;
+        }
+
+        // Round up so the effect stays visible until the timer actually ends
+        (value * self.time_remaining as i64 + self.duration as i64 - 1) / self.duration as i64
+    }
+}
+
+struct ShakeEffect {
+    magnitude: i64,
+    timer: EffectTimer,
+}
+
+struct FlashEffect {
+    color: Color,
+    timer: EffectTimer,
+}
+
+struct BlinkEffect {
+    interval: u128,
+    timer: EffectTimer,
+}
+
+/// Timed, purely visual effects. Any system can trigger an effect, the display controller reads the current state each frame when rendering
+pub struct ScreenEffects {
+    shake: Option<ShakeEffect>,
+    flash: Option<FlashEffect>,
+    blinks: HashMap<Uuid, BlinkEffect>,
+}
+
+impl ScreenEffects {
+    pub fn new() -> Self {
+        Self {
+            shake: None,
+            flash: None,
+            blinks: HashMap::new(),
+        }
+    }
+
+    /// Shakes the camera, the magnitude decays to zero over the duration. A weaker shake won't override a stronger one that is still running
+    ///
+    /// # Arguments
+    ///
+    /// * `magnitude` - The maximum amount of cells the camera is offset by
+    /// * `duration` - How long the shake lasts in milliseconds
+    pub fn trigger_shake(&mut self, magnitude: i64, duration: u128) -> &mut Self {
+        if magnitude >= self.get_shake_magnitude() {
+            self.shake = Some(ShakeEffect {
+                magnitude,
+                timer: EffectTimer::new(duration),
+            });
+        }
+
+        self
+    }
+
+    /// Tints the entire screen with a color that fades out over the duration
+    pub fn trigger_flash(&mut self, color: Color, duration: u128) -> &mut Self {
+        self.flash = Some(FlashEffect {
+            color,
+            timer: EffectTimer::new(duration),
+        });
+
+        self
+    }
+
+    /// Makes the drawable with the provided uuid alternate between hidden and visible
+    ///
+    /// # Arguments
+    ///
+    /// * `uuid` - The uuid of the drawable to blink
+    /// * `duration` - How long the blinking lasts in milliseconds
+    /// * `interval` - How long each hidden or visible period lasts in milliseconds
+    pub fn trigger_blink(&mut self, uuid: Uuid, duration: u128, interval: u128) -> &mut Self {
+        self.blinks.insert(
+            uuid,
+            BlinkEffect {
+                interval,
+                timer: EffectTimer::new(duration),
+            },
+        );
+
+        self
+    }
+
+    /// Advances all effects, removing the ones that have finished
+    pub fn handle_game_loop(&mut self, game_loop_duration: u128) -> &mut Self {
+        if let Some(shake) = self.shake.as_mut() {
+            if !shake.timer.update(game_loop_duration) {
+                self.shake = None;
+            }
+        }
+
+        if let Some(flash) = self.flash.as_mut() {
+            if !flash.timer.update(game_loop_duration) {
+                self.flash = None;
+            }
+        }
+
+        self.blinks
+            .retain(|_, blink| blink.timer.update(game_loop_duration));
+
+        self
+    }
+
+    /// Returns the current, decayed, shake magnitude
+    pub fn get_shake_magnitude(&self) -> i64 {
+        match &self.shake {
+            Some(shake) => shake.timer.decay(shake.magnitude),
+            None => 0,
+        }
+    }
+
+    /// Returns the current flash color faded by how much of the flash is left, None if there is no flash
+    pub fn get_flash_color(&self) -> Option<Color> {
+        let flash = self.flash.as_ref()?;
+
+        let (r, g, b) = color_to_rgb(flash.color)?;
+
+        let fade = |channel: u8| flash.timer.decay(channel as i64) as u8;
+
+        Some(Color::Rgb {
+            r: fade(r),
+            g: fade(g),
+            b: fade(b),
+        })
+    }
+
+    /// Returns all the uuids of drawables that are currently blinked out
+    pub fn get_hidden_uuids(&self) -> Vec<Uuid> {
+        self.blinks
+            .iter()
+            .filter(|(_, blink)| {
+                blink.interval > 0 && (blink.timer.get_time_elapsed() / blink.interval) % 2 == 1
+            })
+            .map(|(uuid, _)| *uuid)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::style::Color;
+    use uuid::Uuid;
+
+    use super::ScreenEffects;
+
+    #[test]
+    fn it_decays_the_shake_magnitude() {
+        let mut screen_effects = ScreenEffects::new();
+
+        screen_effects.trigger_shake(4, 400);
+
+        assert_eq!(screen_effects.get_shake_magnitude(), 4);
+
+        screen_effects.handle_game_loop(200);
+
+        assert_eq!(screen_effects.get_shake_magnitude(), 2);
+
+        screen_effects.handle_game_loop(200);
+
+        assert_eq!(screen_effects.get_shake_magnitude(), 0);
+    }
+
+    #[test]
+    fn it_fades_the_flash_color() {
+        let mut screen_effects = ScreenEffects::new();
+
+        screen_effects.trigger_flash(Color::Rgb { r: 200, g: 0, b: 0 }, 100);
+
+        screen_effects.handle_game_loop(50);
+
+        assert_eq!(
+            screen_effects.get_flash_color(),
+            Some(Color::Rgb { r: 100, g: 0, b: 0 })
+        );
+
+        screen_effects.handle_game_loop(50);
+
+        assert_eq!(screen_effects.get_flash_color(), None);
+    }
+
+    #[test]
+    fn it_alternates_hidden_drawables_while_blinking() {
+        let mut screen_effects = ScreenEffects::new();
+        let uuid = Uuid::new_v4();
+
+        screen_effects.trigger_blink(uuid, 1000, 100);
+
+        assert!(screen_effects.get_hidden_uuids().is_empty());
+
+        screen_effects.handle_game_loop(100);
+
+        assert_eq!(screen_effects.get_hidden_uuids(), vec![uuid]);
+
+        screen_effects.handle_game_loop(100);
+
+        assert!(screen_effects.get_hidden_uuids().is_empty());
+
+        screen_effects.handle_game_loop(1000);
+
+        assert!(screen_effects.get_hidden_uuids().is_empty());
+    }
+}
@@ -0,0 +1,267 @@
+use crate::api::display::Point;
+
+// Default feel of the ship, cells are roughly twice as tall as they are wide so the vertical values are lower to give a similar speed on screen
+/// Maximum speed in cells per second
+pub const SHIP_MAX_SPEED: Point<f64> = Point::new(33.0, 20.0);
+/// Acceleration while thrusting in cells per second per second
+pub const SHIP_ACCELERATION: Point<f64> = Point::new(90.0, 55.0);
+/// Fraction of the velocity lost per second, this is applied continuously so it doesn't depend on the frame rate
+pub const SHIP_DRAG: f64 = 0.75;
+/// When true the ship leaves one edge of the world and comes back on the opposite edge instead of stopping
+pub const SHIP_WRAP_AROUND: bool = false;
+
+/// Tuning values for the ship physics
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShipPhysicsConfig {
+    pub max_speed: Point<f64>,
+    pub acceleration: Point<f64>,
+    pub drag: f64,
+    pub wrap_around: bool,
+}
+
+impl Default for ShipPhysicsConfig {
+    fn default() -> Self {
+        Self {
+            max_speed: SHIP_MAX_SPEED,
+            acceleration: SHIP_ACCELERATION,
+            drag: SHIP_DRAG,
+            wrap_around: SHIP_WRAP_AROUND,
+        }
+    }
+}
+
+/// Inertia based movement, thrust accelerates the ship and drag slowly brings it to a stop once the thrust is released.
+///
+/// The position is tracked with sub-cell precision so low speeds and short game loops still move the ship smoothly
+#[derive(Debug)]
+pub struct ShipPhysics {
+    pub config: ShipPhysicsConfig,
+    position: Point<f64>,
+    velocity: Point<f64>,
+    // Direction of the thrust on each axis, from -1 to 1
+    thrust: Point<f64>,
+}
+
+impl ShipPhysics {
+    pub fn new(config: ShipPhysicsConfig, location: Point<f64>) -> Self {
+        Self {
+            config,
+            position: location,
+            velocity: Point::default(),
+            thrust: Point::default(),
+        }
+    }
+
+    /// Sets the thrust on the horizontal axis, -1 thrusts left and 1 thrusts right
+    pub fn set_thrust_width(&mut self, thrust: f64) -> &mut Self {
+        self.thrust.width = thrust.clamp(-1.0, 1.0);
+
+        self
+    }
+
+    /// Sets the thrust on the vertical axis, -1 thrusts up and 1 thrusts down
+    pub fn set_thrust_height(&mut self, thrust: f64) -> &mut Self {
+        self.thrust.height = thrust.clamp(-1.0, 1.0);
+
+        self
+    }
+
+    pub fn clear_thrust(&mut self) -> &mut Self {
+        self.thrust = Point::default();
+
+        self
+    }
+
+    /// Moves the ship to the location without changing its velocity
+    pub fn set_location(&mut self, location: Point<f64>) -> &mut Self {
+        self.position = location;
+
+        self
+    }
+
+    pub fn stop(&mut self) -> &mut Self {
+        self.velocity = Point::default();
+
+        self
+    }
+
+    pub fn get_location(&self) -> Point<f64> {
+        self.position
+    }
+
+    /// Returns the velocity in cells per second
+    pub fn get_velocity(&self) -> Point<f64> {
+        self.velocity
+    }
+
+    /// Applies the thrust, drag and velocity for the duration of the game loop
+    ///
+    /// # Arguments
+    ///
+    /// * `game_loop_duration` - How long the loop took in milliseconds
+    /// * `min_location` - The smallest location the ship can be at
+    /// * `max_location` - The largest location the ship can be at, the ship either stops at these bounds or wraps around to the opposite bound
+    pub fn handle_game_loop(
+        &mut self,
+        game_loop_duration: u128,
+        min_location: Point<i64>,
+        max_location: Point<i64>,
+    ) -> &mut Self {
+        let seconds = game_loop_duration as f64 / 1000.0;
+        let drag_factor = (1.0 - self.config.drag.clamp(0.0, 1.0)).powf(seconds);
+
+        let update_axis = |velocity: f64, thrust: f64, acceleration: f64, max_speed: f64| {
+            ((velocity + thrust * acceleration * seconds) * drag_factor)
+                .clamp(-max_speed, max_speed)
+        };
+
+        self.velocity = Point::new(
+            update_axis(
+                self.velocity.width,
+                self.thrust.width,
+                self.config.acceleration.width,
+                self.config.max_speed.width,
+            ),
+            update_axis(
+                self.velocity.height,
+                self.thrust.height,
+                self.config.acceleration.height,
+                self.config.max_speed.height,
+            ),
+        );
+
+        let position = self.position + self.velocity * Point::new(seconds, seconds);
+
+        let (width, width_velocity) = self.constrain_axis(
+            position.width,
+            self.velocity.width,
+            min_location.width as f64,
+            max_location.width as f64,
+        );
+        let (height, height_velocity) = self.constrain_axis(
+            position.height,
+            self.velocity.height,
+            min_location.height as f64,
+            max_location.height as f64,
+        );
+
+        self.position = Point::new(width, height);
+        self.velocity = Point::new(width_velocity, height_velocity);
+
+        self
+    }
+
+    /// Keeps a position within the bounds, returning the position and velocity for the axis
+    fn constrain_axis(&self, position: f64, velocity: f64, min: f64, max: f64) -> (f64, f64) {
+        if position >= min && position <= max {
+            return (position, velocity);
+        }
+
+        if self.config.wrap_around && max > min {
+            return (min + (position - min).rem_euclid(max - min), velocity);
+        }
+
+        // Hitting the edge of the world stops the ship on that axis
+        (position.clamp(min, max.max(min)), 0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::display::Point;
+
+    use super::{ShipPhysics, ShipPhysicsConfig, SHIP_MAX_SPEED};
+
+    const MIN_LOCATION: Point<i64> = Point::new(0, 0);
+    const MAX_LOCATION: Point<i64> = Point::new(1000, 1000);
+
+    fn create_physics(config: ShipPhysicsConfig) -> ShipPhysics {
+        ShipPhysics::new(config, Point::new(500.0, 500.0))
+    }
+
+    #[test]
+    fn it_accelerates_up_to_the_max_speed() {
+        let mut physics = create_physics(ShipPhysicsConfig::default());
+
+        physics.set_thrust_width(1.0);
+        physics.handle_game_loop(100, MIN_LOCATION, MAX_LOCATION);
+
+        let velocity = physics.get_velocity().round();
+
+        assert!(velocity.width > 0 && velocity.width < SHIP_MAX_SPEED.width as i64);
+
+        for _ in 0..50 {
+            physics.handle_game_loop(100, MIN_LOCATION, MAX_LOCATION);
+        }
+
+        assert_eq!(
+            physics.get_velocity().round().width,
+            SHIP_MAX_SPEED.width as i64
+        );
+        assert_eq!(physics.get_velocity().round().height, 0);
+    }
+
+    #[test]
+    fn it_slows_down_from_drag_without_thrust() {
+        let mut physics = create_physics(ShipPhysicsConfig::default());
+
+        physics.set_thrust_height(-1.0);
+        physics.handle_game_loop(500, MIN_LOCATION, MAX_LOCATION);
+
+        let thrusting_speed = physics.get_velocity().round().height.abs();
+
+        physics.clear_thrust();
+        physics.handle_game_loop(500, MIN_LOCATION, MAX_LOCATION);
+
+        let drifting_speed = physics.get_velocity().round().height.abs();
+
+        assert!(drifting_speed > 0 && drifting_speed < thrusting_speed);
+    }
+
+    #[test]
+    fn it_accumulates_movement_smaller_than_a_cell() {
+        let mut physics = create_physics(ShipPhysicsConfig {
+            drag: 0.0,
+            ..Default::default()
+        });
+
+        physics.velocity = Point::new(4.0, 0.0);
+
+        // Each loop only moves a fifth of a cell, the ship still moves at the right speed over time
+        for _ in 0..10 {
+            physics.handle_game_loop(50, MIN_LOCATION, MAX_LOCATION);
+        }
+
+        assert_eq!(physics.get_location().round(), Point::new(502, 500));
+    }
+
+    #[test]
+    fn it_stops_or_wraps_at_the_bounds() {
+        let config = ShipPhysicsConfig {
+            drag: 0.0,
+            ..Default::default()
+        };
+
+        let mut physics = ShipPhysics::new(config, Point::new(9.0, 5.0));
+        physics.velocity = Point::new(20.0, 0.0);
+
+        physics.handle_game_loop(100, MIN_LOCATION, Point::new(10, 10));
+
+        assert_eq!(physics.get_location().round(), Point::new(10, 5));
+        assert_eq!(physics.get_velocity().round(), Point::new(0, 0));
+
+        let mut physics = ShipPhysics::new(
+            ShipPhysicsConfig {
+                wrap_around: true,
+                ..config
+            },
+            Point::new(9.0, 5.0),
+        );
+        physics.velocity = Point::new(20.0, 0.0);
+
+        physics.handle_game_loop(100, MIN_LOCATION, Point::new(10, 10));
+
+        assert_eq!(physics.get_location().round(), Point::new(1, 5));
+        assert_eq!(physics.get_velocity().round(), Point::new(20, 0));
+    }
+}
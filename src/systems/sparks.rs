@@ -0,0 +1,143 @@
+use crossterm::style::Color;
+use rand::Rng;
+
+use crate::{
+    api::display::{
+        element::{BlendMode, DEFAULT_BACKGROUND},
+        Element, Layout, Point,
+    },
+    components::{Drawable, DrawableState, DrawableType},
+};
+
+/// Number of sparks spawned for each impact
+const SPARKS_PER_IMPACT: usize = 6;
+/// How long each spark lasts in milliseconds
+const SPARK_LIFETIME: u128 = 300;
+/// Speed of the sparks in cells per second
+const SPARK_SPEED: f64 = 15.0;
+
+const SPARK_ELEMENT: Element =
+    Element::new('*', DEFAULT_BACKGROUND, Color::Yellow).with_blend_mode(BlendMode::KeepBackground);
+
+struct Spark {
+    location: Point<f64>,
+    velocity: Point<f64>,
+    time_remaining: u128,
+}
+
+/// Short lived particles thrown out from impacts. They are stored in a single drawable covering the world with the Background type, so they are ignored by collision detection
+pub struct Sparks {
+    pub drawable: DrawableState,
+    sparks: Vec<Spark>,
+}
+
+impl Sparks {
+    /// # Arguments
+    ///
+    /// * `world_dimensions` - Sparks are drawn anywhere within these dimensions
+    pub fn new(world_dimensions: Point<i64>) -> Self {
+        Self {
+            drawable: DrawableState::new(
+                Layout::new(&world_dimensions, None),
+                Point::default(),
+                DrawableType::Background,
+                None,
+            ),
+            sparks: vec![],
+        }
+    }
+
+    /// Throws sparks out from the impact location, they spread around the direction provided
+    ///
+    /// # Arguments
+    ///
+    /// * `location` - World location of the impact
+    /// * `direction` - The general direction the sparks travel in, sparks spread in every direction if this is zero
+    pub fn spawn(&mut self, location: Point<f64>, direction: Point<f64>) -> &mut Self {
+        let mut rng = rand::thread_rng();
+
+        let base_angle = direction.height.atan2(direction.width);
+        let spread = if direction == Point::default() {
+            std::f64::consts::PI
+        } else {
+            std::f64::consts::FRAC_PI_3
+        };
+
+        for _ in 0..SPARKS_PER_IMPACT {
+            let angle = base_angle + rng.gen_range(-spread..=spread);
+            let speed = SPARK_SPEED * rng.gen_range(0.5..=1.0);
+
+            self.sparks.push(Spark {
+                location,
+                velocity: Point::new(angle.cos() * speed, angle.sin() * speed),
+                time_remaining: SPARK_LIFETIME,
+            });
+        }
+
+        self
+    }
+
+    /// Moves the sparks and removes the ones that have burnt out
+    pub fn handle_game_loop(&mut self, game_loop_duration: u128) -> &mut Self {
+        let seconds = game_loop_duration as f64 / 1000.0;
+
+        self.sparks.retain_mut(|spark| {
+            spark.location = spark.location + spark.velocity * Point::new(seconds, seconds);
+            spark.time_remaining = spark.time_remaining.saturating_sub(game_loop_duration);
+
+            spark.time_remaining > 0
+        });
+
+        self.redraw()
+    }
+
+    fn redraw(&mut self) -> &mut Self {
+        self.drawable.layout.reset();
+
+        for spark in self.sparks.iter() {
+            // Sparks that fly out of the world are left to burn out without being drawn
+            let _ = self
+                .drawable
+                .layout
+                .draw_item(SPARK_ELEMENT, &spark.location.round());
+        }
+
+        self
+    }
+}
+
+impl Drawable for Sparks {
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.set_location(updated_position);
+
+        self
+    }
+
+    fn get_drawable_state(&self) -> &DrawableState {
+        &self.drawable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::display::Point;
+
+    use super::{Sparks, SPARKS_PER_IMPACT, SPARK_LIFETIME};
+
+    #[test]
+    fn it_burns_out_sparks_after_their_lifetime() {
+        let mut sparks = Sparks::new(Point::new(20, 20));
+
+        sparks.spawn(Point::new(10.0, 10.0), Point::new(0.0, -1.0));
+
+        assert_eq!(sparks.sparks.len(), SPARKS_PER_IMPACT);
+
+        sparks.handle_game_loop(SPARK_LIFETIME - 1);
+
+        assert_eq!(sparks.sparks.len(), SPARKS_PER_IMPACT);
+
+        sparks.handle_game_loop(1);
+
+        assert!(sparks.sparks.is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+use crossterm::style::Color;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    api::display::{Element, Layout, Point},
+    components::{Drawable, DrawableState, DrawableType},
+};
+
+struct StarLayerConfig {
+    /// Number of stars for every 1000 cells of the play area
+    density: i64,
+    /// Speed the layer scrolls down in cells per second
+    speed: u128,
+    element: Element,
+}
+
+/// Layers are ordered from furthest to nearest, nearer layers are drawn last so they sit on top
+const STAR_LAYERS: [StarLayerConfig; 3] = [
+    StarLayerConfig {
+        density: 12,
+        speed: 2,
+        element: Element::new('.', Color::Black, Color::DarkGrey),
+    },
+    StarLayerConfig {
+        density: 6,
+        speed: 5,
+        element: Element::new('+', Color::Black, Color::Grey),
+    },
+    StarLayerConfig {
+        density: 2,
+        speed: 12,
+        element: Element::new('*', Color::Black, Color::White),
+    },
+];
+
+struct StarLayer {
+    stars: Vec<Point<i64>>,
+    speed: u128,
+    element: Element,
+    // Time that hasn't been converted into movement yet, so slow layers still move when the game loop is fast
+    time_elapsed_since_move: u128,
+}
+
+/// A scrolling parallax background made up of several layers of stars. The stars are stored in a drawable with the Background type so they are drawn beneath the entities and are ignored by collision detection
+pub struct Starfield {
+    pub drawable: DrawableState,
+    layers: Vec<StarLayer>,
+    dimensions: Point<i64>,
+    rng: StdRng,
+}
+
+impl Starfield {
+    /// Creates a new starfield filling the provided dimensions
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - The dimensions of the play area
+    /// * `seed` - Seed for the star positions, the same seed always produces the same starfield
+    pub fn new(dimensions: Point<i64>, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        let layers = STAR_LAYERS
+            .iter()
+            .map(|config| {
+                let star_count = dimensions.width * dimensions.height * config.density / 1000;
+
+                StarLayer {
+                    stars: (0..star_count)
+                        .map(|_| Point {
+                            width: rng.gen_range(0..dimensions.width),
+                            height: rng.gen_range(0..dimensions.height),
+                        })
+                        .collect(),
+                    speed: config.speed,
+                    element: config.element,
+                    time_elapsed_since_move: 0,
+                }
+            })
+            .collect();
+
+        let mut starfield = Self {
+            drawable: DrawableState::new(
+                Layout::new(&dimensions, None),
+                Point::default(),
+                DrawableType::Background,
+                None,
+            ),
+            layers,
+            dimensions,
+            rng,
+        };
+
+        starfield.redraw();
+
+        starfield
+    }
+
+    /// Scrolls each layer down based on its speed, stars that leave the bottom are moved back to the top with a new column
+    pub fn handle_game_loop(&mut self, game_loop_duration: u128) -> &mut Self {
+        for layer in self.layers.iter_mut() {
+            layer.time_elapsed_since_move += game_loop_duration;
+
+            let cells_to_move = layer.time_elapsed_since_move * layer.speed / 1000;
+
+            if cells_to_move == 0 {
+                continue;
+            }
+
+            layer.time_elapsed_since_move -= cells_to_move * 1000 / layer.speed;
+
+            for star in layer.stars.iter_mut() {
+                star.height += cells_to_move as i64;
+
+                if star.height >= self.dimensions.height {
+                    star.height -= self.dimensions.height;
+                    star.width = self.rng.gen_range(0..self.dimensions.width);
+                }
+            }
+        }
+
+        self.redraw()
+    }
+
+    fn redraw(&mut self) -> &mut Self {
+        self.drawable.layout.reset();
+
+        for layer in self.layers.iter() {
+            for star in layer.stars.iter() {
+                // Stars are always kept within the dimensions so this can't fail
+                self.drawable.layout.draw_item(layer.element, star).unwrap();
+            }
+        }
+
+        self
+    }
+}
+
+impl Drawable for Starfield {
+    fn set_position(&mut self, updated_position: Point<f64>) -> &mut Self {
+        self.drawable.set_location(updated_position);
+
+        self
+    }
+
+    fn get_drawable_state(&self) -> &DrawableState {
+        &self.drawable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::display::Point;
+
+    use super::Starfield;
+
+    const DIMENSIONS: Point<i64> = Point::new(60, 40);
+
+    #[test]
+    fn it_creates_the_same_starfield_for_the_same_seed() {
+        let first = Starfield::new(DIMENSIONS, 42);
+        let second = Starfield::new(DIMENSIONS, 42);
+
+        assert_eq!(first.drawable.layout, second.drawable.layout);
+    }
+
+    #[test]
+    fn it_scrolls_the_nearest_layer_faster_than_the_furthest() {
+        let mut starfield = Starfield::new(DIMENSIONS, 42);
+
+        let furthest_before = starfield.layers[0].stars.clone();
+        let nearest_before = starfield.layers[2].stars.clone();
+
+        starfield.handle_game_loop(250);
+
+        // 250ms at 2 cells per second isn't enough to move a cell yet
+        assert_eq!(starfield.layers[0].stars, furthest_before);
+
+        for (before, after) in nearest_before.iter().zip(starfield.layers[2].stars.iter()) {
+            if before.height + 3 < DIMENSIONS.height {
+                assert_eq!(after.height, before.height + 3);
+            }
+        }
+    }
+}
@@ -0,0 +1,497 @@
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+
+use crate::api::display::Point;
+
+/// Time between waves in milliseconds, the banner for the next wave is shown for this long
+pub const WAVE_INTERMISSION_DURATION: u128 = 3000;
+
+/// How much each cycle through the wave list adds to the number of enemies in a wave
+const CYCLE_COUNT_SCALE: f64 = 0.25;
+/// Each cycle through the wave list multiplies the time between spawns by this
+const CYCLE_INTERVAL_SCALE: f64 = 0.85;
+/// How much each cycle through the wave list adds to the speed of the asteroids
+const CYCLE_VELOCITY_SCALE: f64 = 0.15;
+
+/// The range asteroid velocities are picked from, in cells per second
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VelocityRange {
+    pub min: Point<f64>,
+    pub max: Point<f64>,
+}
+
+impl VelocityRange {
+    pub const fn new(min: Point<f64>, max: Point<f64>) -> Self {
+        Self { min, max }
+    }
+
+    /// Picks a velocity from within the range, an empty range always gives its minimum
+    pub fn get_velocity(&self, rng: &mut impl Rng) -> Point<f64> {
+        Point::new(
+            get_in_range(rng, self.min.width, self.max.width),
+            get_in_range(rng, self.min.height, self.max.height),
+        )
+    }
+}
+
+fn get_in_range(rng: &mut impl Rng, min: f64, max: f64) -> f64 {
+    if min < max {
+        rng.gen_range(min..max)
+    } else {
+        min
+    }
+}
+
+/// What the player has to do for a wave to end
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaveClearCondition {
+    /// Everything in the wave has spawned and no enemies are left in the world
+    ClearField,
+    /// The wave ends once it has lasted this many milliseconds, anything that hasn't spawned yet is dropped
+    Survive(u128),
+}
+
+/// When a boss wave's boss appears
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BossTrigger {
+    /// The boss spawns before anything else in the wave
+    WaveStart,
+    /// The boss spawns after everything else in the wave
+    WaveEnd,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WaveDefinition {
+    pub asteroid_count: u32,
+    pub enemy_ship_count: u32,
+    pub seeker_mine_count: u32,
+    /// None for waves without a boss
+    pub boss: Option<BossTrigger>,
+    /// Time before the wave starts in milliseconds, the wave's banner is shown for this long
+    pub intermission_duration: u128,
+    /// Time between spawns in milliseconds
+    pub spawn_interval: u128,
+    /// Columns enemies spawn in as fractions of the world's width from 0 to 1, empty spawns them anywhere
+    pub spawn_lanes: Vec<f64>,
+    pub asteroid_velocity: VelocityRange,
+    pub clear_condition: WaveClearCondition,
+}
+
+impl Default for WaveDefinition {
+    /// An empty wave, its asteroids fly at the velocities of the original endless mode
+    fn default() -> Self {
+        Self {
+            asteroid_count: 0,
+            enemy_ship_count: 0,
+            seeker_mine_count: 0,
+            boss: None,
+            intermission_duration: WAVE_INTERMISSION_DURATION,
+            spawn_interval: 1000,
+            spawn_lanes: vec![],
+            asteroid_velocity: VelocityRange::new(Point::new(-20.0, 1.0), Point::new(20.0, 40.0)),
+            clear_condition: WaveClearCondition::ClearField,
+        }
+    }
+}
+
+impl WaveDefinition {
+    /// Returns a harder copy of the wave for the given cycle through the wave list, cycle 0 is the wave as defined
+    pub fn get_scaled(&self, cycle: u32) -> Self {
+        let count_scale = 1.0 + CYCLE_COUNT_SCALE * cycle as f64;
+        let velocity_scale = 1.0 + CYCLE_VELOCITY_SCALE * cycle as f64;
+        let scale_count = |count: u32| (count as f64 * count_scale) as u32;
+
+        Self {
+            asteroid_count: scale_count(self.asteroid_count),
+            enemy_ship_count: scale_count(self.enemy_ship_count),
+            seeker_mine_count: scale_count(self.seeker_mine_count),
+            boss: self.boss,
+            intermission_duration: self.intermission_duration,
+            spawn_interval: (self.spawn_interval as f64 * CYCLE_INTERVAL_SCALE.powi(cycle as i32))
+                as u128,
+            spawn_lanes: self.spawn_lanes.clone(),
+            asteroid_velocity: VelocityRange::new(
+                self.asteroid_velocity.min * Point::new(velocity_scale, velocity_scale),
+                self.asteroid_velocity.max * Point::new(velocity_scale, velocity_scale),
+            ),
+            clear_condition: self.clear_condition,
+        }
+    }
+}
+
+/// The waves of a run, once the last wave is cleared they repeat with more, faster enemies
+pub const DEFAULT_WAVES: [WaveDefinition; 6] = [
+    WaveDefinition {
+        asteroid_count: 8,
+        enemy_ship_count: 0,
+        seeker_mine_count: 0,
+        boss: None,
+        intermission_duration: WAVE_INTERMISSION_DURATION,
+        spawn_interval: 1500,
+        spawn_lanes: Vec::new(),
+        asteroid_velocity: VelocityRange::new(Point::new(-10.0, 5.0), Point::new(10.0, 20.0)),
+        clear_condition: WaveClearCondition::ClearField,
+    },
+    WaveDefinition {
+        asteroid_count: 12,
+        enemy_ship_count: 2,
+        seeker_mine_count: 0,
+        boss: None,
+        intermission_duration: WAVE_INTERMISSION_DURATION,
+        spawn_interval: 1200,
+        spawn_lanes: Vec::new(),
+        asteroid_velocity: VelocityRange::new(Point::new(-15.0, 5.0), Point::new(15.0, 25.0)),
+        clear_condition: WaveClearCondition::ClearField,
+    },
+    WaveDefinition {
+        asteroid_count: 6,
+        enemy_ship_count: 0,
+        seeker_mine_count: 1,
+        boss: Some(BossTrigger::WaveStart),
+        intermission_duration: WAVE_INTERMISSION_DURATION,
+        spawn_interval: 1500,
+        spawn_lanes: Vec::new(),
+        asteroid_velocity: VelocityRange::new(Point::new(-10.0, 5.0), Point::new(10.0, 20.0)),
+        clear_condition: WaveClearCondition::ClearField,
+    },
+    WaveDefinition {
+        asteroid_count: 14,
+        enemy_ship_count: 3,
+        seeker_mine_count: 2,
+        boss: None,
+        intermission_duration: WAVE_INTERMISSION_DURATION,
+        spawn_interval: 1000,
+        spawn_lanes: Vec::new(),
+        asteroid_velocity: VelocityRange::new(Point::new(-20.0, 10.0), Point::new(20.0, 30.0)),
+        clear_condition: WaveClearCondition::Survive(30000),
+    },
+    WaveDefinition {
+        asteroid_count: 16,
+        enemy_ship_count: 4,
+        seeker_mine_count: 3,
+        boss: None,
+        intermission_duration: WAVE_INTERMISSION_DURATION,
+        spawn_interval: 900,
+        spawn_lanes: Vec::new(),
+        asteroid_velocity: VelocityRange::new(Point::new(-20.0, 10.0), Point::new(20.0, 35.0)),
+        clear_condition: WaveClearCondition::ClearField,
+    },
+    WaveDefinition {
+        asteroid_count: 10,
+        enemy_ship_count: 2,
+        seeker_mine_count: 2,
+        boss: Some(BossTrigger::WaveStart),
+        intermission_duration: WAVE_INTERMISSION_DURATION,
+        spawn_interval: 1200,
+        spawn_lanes: Vec::new(),
+        asteroid_velocity: VelocityRange::new(Point::new(-20.0, 10.0), Point::new(20.0, 30.0)),
+        clear_condition: WaveClearCondition::ClearField,
+    },
+];
+
+/// Something the wave director wants spawned. Lanes are fractions of the world's width, None leaves the column up to the controller
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WaveSpawn {
+    Asteroid {
+        lane: Option<f64>,
+        velocity: Point<f64>,
+    },
+    EnemyShip {
+        lane: Option<f64>,
+    },
+    SeekerMine {
+        lane: Option<f64>,
+    },
+    Boss,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WavePhase {
+    /// Milliseconds left before the wave starts
+    Intermission(u128),
+    Active,
+}
+
+/// Runs the waves of a game, deciding what spawns and when, and moving on to the next wave once the current one is cleared
+pub struct WaveDirector {
+    waves: Vec<WaveDefinition>,
+    /// The wave being played, or the next wave during an intermission. Starts at 1
+    wave: u32,
+    phase: WavePhase,
+    current_wave: WaveDefinition,
+    // Spawns are popped from the back of the queue
+    spawn_queue: Vec<WaveSpawn>,
+    time_elapsed_since_spawn: u128,
+    wave_time_elapsed: u128,
+    // Spawn order and asteroid velocities come from this so a game's waves can be reproduced from its seed
+    rng: StdRng,
+}
+
+impl WaveDirector {
+    /// Creates a new wave director, the first wave starts after an intermission
+    ///
+    /// # Arguments
+    ///
+    /// * `waves` - The waves to play in order, these repeat with scaled difficulty once they run out. Must not be empty
+    /// * `seed` - Seed for the waves, the same seed always spawns the same waves
+    pub fn new(waves: Vec<WaveDefinition>, seed: u64) -> Self {
+        let current_wave = waves[0].clone();
+
+        Self {
+            phase: WavePhase::Intermission(current_wave.intermission_duration),
+            waves,
+            wave: 1,
+            current_wave,
+            spawn_queue: vec![],
+            time_elapsed_since_spawn: 0,
+            wave_time_elapsed: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the definition of the wave with difficulty scaling applied, waves are numbered from 1
+    pub fn get_wave_definition(&self, wave: u32) -> WaveDefinition {
+        let index = (wave.max(1) - 1) as usize;
+        let cycle = (index / self.waves.len()) as u32;
+
+        self.waves[index % self.waves.len()].get_scaled(cycle)
+    }
+
+    pub fn get_wave_number(&self) -> u32 {
+        self.wave
+    }
+
+    pub fn is_intermission(&self) -> bool {
+        matches!(self.phase, WavePhase::Intermission(_))
+    }
+
+    /// Returns the banner announcing the next wave, None while a wave is being played
+    pub fn get_banner(&self) -> Option<String> {
+        if !self.is_intermission() {
+            return None;
+        }
+
+        if self.get_wave_definition(self.wave).boss.is_some() {
+            Some(format!("WAVE {} - BOSS INCOMING", self.wave))
+        } else {
+            Some(format!("WAVE {}", self.wave))
+        }
+    }
+
+    fn start_wave(&mut self) {
+        let wave = self.get_wave_definition(self.wave);
+
+        let mut spawn_queue = vec![];
+
+        for _ in 0..wave.asteroid_count {
+            spawn_queue.push(WaveSpawn::Asteroid {
+                lane: wave.spawn_lanes.choose(&mut self.rng).copied(),
+                velocity: wave.asteroid_velocity.get_velocity(&mut self.rng),
+            });
+        }
+
+        for _ in 0..wave.enemy_ship_count {
+            spawn_queue.push(WaveSpawn::EnemyShip {
+                lane: wave.spawn_lanes.choose(&mut self.rng).copied(),
+            });
+        }
+
+        for _ in 0..wave.seeker_mine_count {
+            spawn_queue.push(WaveSpawn::SeekerMine {
+                lane: wave.spawn_lanes.choose(&mut self.rng).copied(),
+            });
+        }
+
+        spawn_queue.shuffle(&mut self.rng);
+
+        // The back of the queue spawns first
+        match wave.boss {
+            Some(BossTrigger::WaveStart) => spawn_queue.push(WaveSpawn::Boss),
+            Some(BossTrigger::WaveEnd) => spawn_queue.insert(0, WaveSpawn::Boss),
+            None => {}
+        }
+
+        // The first spawn happens straight away
+        self.time_elapsed_since_spawn = wave.spawn_interval;
+        self.current_wave = wave;
+        self.spawn_queue = spawn_queue;
+        self.phase = WavePhase::Active;
+        self.wave_time_elapsed = 0;
+    }
+
+    fn is_wave_cleared(&self, enemies_remaining: usize) -> bool {
+        match self.current_wave.clear_condition {
+            WaveClearCondition::ClearField => self.spawn_queue.is_empty() && enemies_remaining == 0,
+            WaveClearCondition::Survive(duration) => self.wave_time_elapsed >= duration,
+        }
+    }
+
+    /// Advances the intermission or the current wave
+    ///
+    /// # Arguments
+    ///
+    /// * `game_loop_duration` - How long the loop took in milliseconds
+    /// * `enemies_remaining` - Number of enemies still in the world, used by waves that need the field cleared
+    ///
+    /// # Returns
+    ///
+    /// What should be spawned this loop, if anything
+    pub fn handle_game_loop(
+        &mut self,
+        game_loop_duration: u128,
+        enemies_remaining: usize,
+    ) -> Option<WaveSpawn> {
+        if let WavePhase::Intermission(time_remaining) = self.phase {
+            if time_remaining > game_loop_duration {
+                self.phase = WavePhase::Intermission(time_remaining - game_loop_duration);
+
+                return None;
+            }
+
+            self.start_wave();
+        } else {
+            self.wave_time_elapsed += game_loop_duration;
+            self.time_elapsed_since_spawn += game_loop_duration;
+        }
+
+        if self.is_wave_cleared(enemies_remaining) {
+            self.wave += 1;
+            self.spawn_queue.clear();
+            self.phase =
+                WavePhase::Intermission(self.get_wave_definition(self.wave).intermission_duration);
+
+            return None;
+        }
+
+        if self.time_elapsed_since_spawn < self.current_wave.spawn_interval {
+            return None;
+        }
+
+        let spawn = self.spawn_queue.pop()?;
+
+        self.time_elapsed_since_spawn = 0;
+
+        Some(spawn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::api::display::Point;
+
+    use super::{
+        BossTrigger, VelocityRange, WaveClearCondition, WaveDefinition, WaveDirector, WaveSpawn,
+        WAVE_INTERMISSION_DURATION,
+    };
+
+    const WAVE: WaveDefinition = WaveDefinition {
+        asteroid_count: 2,
+        enemy_ship_count: 1,
+        seeker_mine_count: 0,
+        boss: Some(BossTrigger::WaveStart),
+        intermission_duration: WAVE_INTERMISSION_DURATION,
+        spawn_interval: 100,
+        spawn_lanes: Vec::new(),
+        asteroid_velocity: VelocityRange::new(Point::new(-5.0, 5.0), Point::new(5.0, 10.0)),
+        clear_condition: WaveClearCondition::ClearField,
+    };
+
+    fn get_spawns(director: &mut WaveDirector) -> Vec<WaveSpawn> {
+        let mut spawns = vec![];
+        let mut spawn = director.handle_game_loop(WAVE_INTERMISSION_DURATION, 0);
+
+        while let Some(next_spawn) = spawn {
+            spawns.push(next_spawn);
+            spawn = director.handle_game_loop(100, 1);
+        }
+
+        spawns
+    }
+
+    #[test]
+    fn it_spawns_the_wave_after_the_intermission() {
+        let mut director = WaveDirector::new(vec![WAVE], 1);
+
+        assert_eq!(director.get_banner().unwrap(), "WAVE 1 - BOSS INCOMING");
+        assert_eq!(director.handle_game_loop(1000, 0), None);
+
+        let spawns = get_spawns(&mut director);
+
+        assert!(director.get_banner().is_none());
+        assert_eq!(spawns.len(), 4);
+        assert_eq!(spawns[0], WaveSpawn::Boss);
+        assert!(spawns.contains(&WaveSpawn::EnemyShip { lane: None }));
+        assert!(spawns.iter().all(|spawn| match spawn {
+            WaveSpawn::Asteroid { velocity, .. } => {
+                velocity.height >= 5.0 && velocity.height < 10.0
+            }
+            _ => true,
+        }));
+    }
+
+    #[test]
+    fn it_spawns_in_lanes_with_the_boss_last() {
+        let mut director = WaveDirector::new(
+            vec![WaveDefinition {
+                boss: Some(BossTrigger::WaveEnd),
+                spawn_lanes: vec![0.25, 0.75],
+                ..WAVE
+            }],
+            1,
+        );
+
+        let spawns = get_spawns(&mut director);
+
+        assert_eq!(spawns.last(), Some(&WaveSpawn::Boss));
+        assert!(spawns.iter().all(|spawn| match spawn {
+            WaveSpawn::Asteroid { lane, .. } | WaveSpawn::EnemyShip { lane } => {
+                *lane == Some(0.25) || *lane == Some(0.75)
+            }
+            _ => true,
+        }));
+    }
+
+    #[test]
+    fn it_clears_field_waves_once_no_enemies_remain() {
+        let mut director = WaveDirector::new(vec![WAVE], 1);
+
+        get_spawns(&mut director);
+
+        director.handle_game_loop(100, 1);
+        assert_eq!(director.get_wave_number(), 1);
+
+        director.handle_game_loop(100, 0);
+        assert_eq!(director.get_wave_number(), 2);
+        assert!(director.is_intermission());
+    }
+
+    #[test]
+    fn it_clears_survive_waves_after_the_duration() {
+        let mut director = WaveDirector::new(
+            vec![WaveDefinition {
+                clear_condition: WaveClearCondition::Survive(1000),
+                ..WAVE
+            }],
+            1,
+        );
+
+        director.handle_game_loop(WAVE_INTERMISSION_DURATION, 0);
+        director.handle_game_loop(500, 3);
+        assert_eq!(director.get_wave_number(), 1);
+
+        director.handle_game_loop(500, 3);
+        assert_eq!(director.get_wave_number(), 2);
+    }
+
+    #[test]
+    fn it_scales_repeated_waves() {
+        let director = WaveDirector::new(vec![WAVE], 1);
+
+        let first = director.get_wave_definition(1);
+        let repeated = director.get_wave_definition(5);
+
+        assert_eq!(first, WAVE);
+        assert_eq!(repeated.asteroid_count, 4);
+        assert!(repeated.spawn_interval < first.spawn_interval);
+        assert!(repeated.asteroid_velocity.max.height > first.asteroid_velocity.max.height);
+    }
+}
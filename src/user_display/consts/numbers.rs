@@ -58,5 +58,4 @@ pub const NINE: &str = "999999
     99
 999999";
 
-pub const NUMBER_VECTOR: [&'static str; 10] =
-    [ZERO, ONE, TWO, THREE, FOUR, FIVE, SIX, SEVEN, EIGHT, NINE];
+pub const NUMBER_VECTOR: [&str; 10] = [ZERO, ONE, TWO, THREE, FOUR, FIVE, SIX, SEVEN, EIGHT, NINE];
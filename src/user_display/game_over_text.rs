@@ -7,9 +7,3 @@ $$ |  $$ |$$  __$$ |$$ | $$ | $$ |$$   ____|      $$ |  $$ |  \\$$$  /  $$   ___
 \\$$$$$$  |\\$$$$$$$ |$$ | $$ | $$ |\\$$$$$$$\\        $$$$$$  |   \\$  /   \\$$$$$$$\\ $$ |      
  \\______/  \\_______|\\__| \\__| \\__| \\_______|       \\______/     \\_/     \\_______|\\__|      
 ";
-
-// https://fsymbols.com/draw/
-const TEXT: &str = "╔══╗        ╔═╗                    
-║╔═╬═╗╔══╦═╗║║╠═╦═╦═╦╦╗            
-║╚╗║╬╚╣║║║╩╣║║╠╗║╔╣╩╣╔╝            
-╚══╩══╩╩╩╩═╝╚═╝╚═╝╚═╩╝";